@@ -0,0 +1,307 @@
+#!/bin/bash
+
+# CLI execution script for launchd, systemd user timers and crontab
+# This script is triggered by the scheduler backend at the scheduled time. The tool
+# ("claude", "codex" or "gemini") comes from the first argument or the job's TOOL.
+
+TOOL="${1:-$TOOL}"
+case "$TOOL" in
+    claude) TOOL_NAME="Claude" ;;
+    codex) TOOL_NAME="Codex" ;;
+    gemini) TOOL_NAME="Gemini" ;;
+    *)
+        echo "Unknown tool: ${TOOL:-unset}" >&2
+        exit 1
+        ;;
+esac
+# Name of the tool's variables, e.g. CLAUDE_COMMAND and CLAUDE_OPTIONS
+TOOL_PREFIX=$(printf '%s' "$TOOL" | tr '[:lower:]' '[:upper:]')
+
+# Config directory: set by the app in every job's environment; the fallback mirrors the
+# app's default for jobs registered before it was passed along
+if [ -n "$TAURI_CLI_SCHEDULER_ROOT" ]; then
+    CONFIG_DIR="$TAURI_CLI_SCHEDULER_ROOT"
+elif [ "$(uname)" = "Darwin" ]; then
+    CONFIG_DIR="$HOME/Library/Application Support/tauri-cli-scheduler"
+else
+    CONFIG_DIR="${XDG_CONFIG_HOME:-$HOME/.config}/tauri-cli-scheduler"
+fi
+
+# Logging setup
+LOG_DIR="$CONFIG_DIR/logs"
+mkdir -p "$LOG_DIR"
+LOG_FILE="$LOG_DIR/$TOOL-$(date +%Y%m%d-%H%M%S).log"
+ERROR_FILE="$LOG_DIR/$TOOL-$(date +%Y%m%d-%H%M%S).error.log"
+HISTORY_FILE="$CONFIG_DIR/schedule-history.jsonl"
+
+append_history() {
+    local status="$1"
+    if [ -z "$SCHEDULE_ID" ]; then
+        return
+    fi
+    local timestamp
+    timestamp=$(date -u +"%Y-%m-%dT%H:%M:%SZ")
+    printf '%s\n' "{\"timestamp\":\"$timestamp\",\"schedule_id\":\"$SCHEDULE_ID\",\"tool\":\"$TOOL\",\"status\":\"$status\"}" >> "$HISTORY_FILE"
+}
+
+# 'date' in the schedule's own time zone when one is set
+schedule_date() {
+    if [ -n "$SCHEDULE_TIMEZONE" ]; then
+        TZ="$SCHEDULE_TIMEZONE" date "$@"
+    else
+        date "$@"
+    fi
+}
+
+# Count successful runs (launchd and catch-up) recorded for this schedule
+count_successes() {
+    if [ ! -f "$HISTORY_FILE" ]; then
+        echo 0
+        return
+    fi
+    grep -F "\"schedule_id\":\"$SCHEDULE_ID\"" "$HISTORY_FILE" | grep -F "\"tool\":\"$TOOL\"" \
+        | grep -cE '"status":"(catchup-)?success"'
+}
+
+# Unload this schedule from its scheduler backend and remove its job files
+unregister_self() {
+    # The app also drops the schedule from its store; the manual cleanup below is for
+    # when the scheduler binary is gone
+    if [ -n "$SCHEDULER_BIN" ] && [ -x "$SCHEDULER_BIN" ] \
+        && "$SCHEDULER_BIN" unregister >> "$LOG_FILE" 2>> "$ERROR_FILE"; then
+        return
+    fi
+    if [ "$TAURI_CLI_SCHEDULER_BACKEND" = "systemd" ]; then
+        UNIT_NAME="tauri-cli-scheduler-${TOOL}-${SCHEDULE_ID}"
+        UNITS_DIR="${TAURI_CLI_SCHEDULER_AGENTS_DIR:-${XDG_CONFIG_HOME:-$HOME/.config}/systemd/user}"
+
+        # Stop the timer and remove both units (best effort, ignore errors)
+        systemctl --user disable --now "$UNIT_NAME.timer" 2>/dev/null || true
+        rm -f "$UNITS_DIR/$UNIT_NAME.timer" "$UNITS_DIR/$UNIT_NAME.service" 2>/dev/null || true
+        systemctl --user daemon-reload 2>/dev/null || true
+        return
+    fi
+    if [ "$TAURI_CLI_SCHEDULER_BACKEND" = "crontab" ]; then
+        # Drop this schedule's lines from the managed block and its env file
+        crontab -l 2>/dev/null | grep -v -e "#job=${TOOL}:${SCHEDULE_ID}\$" | crontab - 2>/dev/null || true
+        rm -f "$SCHEDULE_ENV_FILE" 2>/dev/null || true
+        return
+    fi
+
+    PLIST_NAME="com.shoma.tauri-cli-scheduler.${TOOL}.${SCHEDULE_ID}.plist"
+    LAUNCH_AGENTS_DIR="${TAURI_CLI_SCHEDULER_AGENTS_DIR:-$HOME/Library/LaunchAgents}"
+    LAUNCH_AGENTS_PLIST="$LAUNCH_AGENTS_DIR/$PLIST_NAME"
+    CONFIG_PLIST="$CONFIG_DIR/$PLIST_NAME"
+
+    # Unload from launchd (best effort, ignore errors)
+    launchctl bootout "gui/$(id -u)" "$LAUNCH_AGENTS_PLIST" 2>/dev/null || true
+
+    # Remove plist files
+    rm -f "$LAUNCH_AGENTS_PLIST" 2>/dev/null || true
+    rm -f "$CONFIG_PLIST" 2>/dev/null || true
+}
+
+# Jobs only name their schedule; its settings come from the app's schedule store. Jobs
+# written before the store existed still carry them in their own environment.
+COMMAND_VAR="${TOOL_PREFIX}_COMMAND"
+OPTIONS_VAR="${TOOL_PREFIX}_OPTIONS"
+if [ -z "${!COMMAND_VAR}" ]; then
+    if [ -z "$SCHEDULER_BIN" ] || [ ! -x "$SCHEDULER_BIN" ] \
+        || ! SCHEDULE_ENV=$("$SCHEDULER_BIN" schedule-env 2>> "$ERROR_FILE"); then
+        echo "=== Failed to load schedule $SCHEDULE_ID (scheduler binary: ${SCHEDULER_BIN:-unset}), re-register this schedule ===" >> "$ERROR_FILE"
+        append_history "failure"
+        exit 1
+    fi
+    eval "$SCHEDULE_ENV"
+fi
+
+# Exported for the login shell the tool runs in
+export TOOL_COMMAND="${!COMMAND_VAR}"
+export TOOL_OPTIONS="${!OPTIONS_VAR}"
+
+# Log execution start
+{
+    echo "=== $TOOL_NAME execution started at $(date) ==="
+    echo "Target directory: $TARGET_DIRECTORY"
+    echo "Command: $TOOL_COMMAND"
+    echo "Options: $TOOL_OPTIONS"
+} >> "$LOG_FILE"
+append_history "wake-triggered"
+
+# Stop schedules that are past their end date or have used up their run count
+TODAY=$(schedule_date +%Y-%m-%d)
+if [ -n "$SCHEDULE_END_DATE" ] && [[ "$TODAY" > "$SCHEDULE_END_DATE" ]]; then
+    echo "=== Skipping execution: End date $SCHEDULE_END_DATE has passed, unregistering schedule ===" >> "$LOG_FILE"
+    append_history "skipped-expired"
+    unregister_self
+    exit 0
+fi
+if [ -n "$SCHEDULE_MAX_RUNS" ] && [ "$(count_successes)" -ge "$SCHEDULE_MAX_RUNS" ]; then
+    echo "=== Skipping execution: Maximum run count $SCHEDULE_MAX_RUNS reached, unregistering schedule ===" >> "$LOG_FILE"
+    append_history "skipped-expired"
+    unregister_self
+    exit 0
+fi
+
+# Check sub-daily interval window if configured (launchd fires these via StartInterval)
+if [ "$SCHEDULE_TYPE" = "interval" ] && [ -n "$SCHEDULE_ACTIVE_START" ] && [ -n "$SCHEDULE_ACTIVE_END" ]; then
+    NOW_HM=$(schedule_date +%H:%M)
+    IN_WINDOW=0
+    if [[ "$SCHEDULE_ACTIVE_START" < "$SCHEDULE_ACTIVE_END" ]]; then
+        # e.g. 09:00-18:00
+        if [[ ! "$NOW_HM" < "$SCHEDULE_ACTIVE_START" && ! "$NOW_HM" > "$SCHEDULE_ACTIVE_END" ]]; then
+            IN_WINDOW=1
+        fi
+    elif [[ ! "$NOW_HM" < "$SCHEDULE_ACTIVE_START" || ! "$NOW_HM" > "$SCHEDULE_ACTIVE_END" ]]; then
+        # Window wraps midnight, e.g. 22:00-06:00
+        IN_WINDOW=1
+    fi
+    if [ $IN_WINDOW -eq 0 ]; then
+        echo "=== Skipping execution: Outside active window ($SCHEDULE_ACTIVE_START-$SCHEDULE_ACTIVE_END) ===" >> "$LOG_FILE"
+        append_history "skipped"
+        exit 0
+    fi
+fi
+
+# Interval and monthly rules and cron expressions that AND their day fields (such as
+# `0 9 */2 * 1`) cannot be expressed in launchd alone, zoned schedules
+# fire daily at translated local times, and blackout calendars live in the app's
+# config; launchd fires on every candidate day and the app decides whether this run
+# actually belongs to the schedule
+if [ "$SCHEDULE_TYPE" = "interval" ] || [ "$SCHEDULE_TYPE" = "monthly" ] \
+    || [ "$SCHEDULE_TYPE" = "cron" ] || [ -n "$SCHEDULE_TIMEZONE" ] \
+    || [ -n "$SCHEDULE_BLACKOUT_CALENDARS" ]; then
+    if [ -z "$SCHEDULER_BIN" ] || [ ! -x "$SCHEDULER_BIN" ]; then
+        echo "=== Skipping execution: Scheduler binary not found (${SCHEDULER_BIN:-unset}), re-register this schedule ===" >> "$ERROR_FILE"
+        append_history "skipped"
+        exit 0
+    fi
+    "$SCHEDULER_BIN" check-due >> "$LOG_FILE" 2>> "$ERROR_FILE"
+    DUE_STATUS=$?
+    if [ $DUE_STATUS -eq 3 ]; then
+        echo "=== Skipping execution: Today is a blackout day ===" >> "$LOG_FILE"
+        append_history "skipped-blackout"
+        exit 0
+    fi
+    if [ $DUE_STATUS -ne 0 ]; then
+        echo "=== Skipping execution: Not a scheduled run (check-due exit code $DUE_STATUS) ===" >> "$LOG_FILE"
+        append_history "skipped"
+        exit 0
+    fi
+fi
+
+# Spread out schedules that share a fire time: wait a random but per-day stable
+# number of seconds (derived from the schedule ID and date) within the jitter window.
+# The in-process backend computes the same delay (in_process_manager::jitter_delay).
+if [ -n "$SCHEDULE_JITTER_MINUTES" ] && [ "$SCHEDULE_JITTER_MINUTES" -gt 0 ]; then
+    JITTER_SEED=$(printf '%s' "$SCHEDULE_ID-$TODAY" | cksum | cut -d ' ' -f 1)
+    JITTER_SECONDS=$((JITTER_SEED % (SCHEDULE_JITTER_MINUTES * 60 + 1)))
+    echo "=== Delaying start by ${JITTER_SECONDS}s (jitter window ${SCHEDULE_JITTER_MINUTES}m) ===" >> "$LOG_FILE"
+    sleep "$JITTER_SECONDS"
+fi
+append_history "started"
+
+# How each tool takes a prompt, in iTerm and headless
+case "$TOOL" in
+    claude)
+        ITERM_COMMAND="claude $TOOL_OPTIONS \\\"$TOOL_COMMAND\\\""
+        HEADLESS_COMMAND='claude $TOOL_OPTIONS -p "$TOOL_COMMAND"'
+        ;;
+    codex)
+        ITERM_COMMAND="codex $TOOL_OPTIONS \\\"$TOOL_COMMAND\\\""
+        HEADLESS_COMMAND='codex exec $TOOL_OPTIONS "$TOOL_COMMAND"'
+        ;;
+    gemini)
+        ITERM_COMMAND="gemini $TOOL_OPTIONS --prompt \\\"$TOOL_COMMAND\\\""
+        HEADLESS_COMMAND='gemini $TOOL_OPTIONS --prompt "$TOOL_COMMAND"'
+        ;;
+esac
+
+if [ "$(uname)" = "Darwin" ]; then
+    # The tool writes its exit status here when it finishes in iTerm
+    STATUS_FILE="$LOG_DIR/$TOOL-$SCHEDULE_ID-$$.status"
+    rm -f "$STATUS_FILE"
+    ITERM_COMMAND="$ITERM_COMMAND; echo \$? > \\\"$STATUS_FILE\\\""
+
+    # Execute AppleScript to launch iTerm and run the tool
+    osascript <<APPLESCRIPT >> "$LOG_FILE" 2>> "$ERROR_FILE"
+tell application "iTerm"
+    activate
+
+    -- Create new window
+    create window with default profile
+
+    tell current session of current window
+        -- Navigate to target directory
+        write text "cd \"$TARGET_DIRECTORY\""
+
+        -- Execute the tool's command
+        write text "$ITERM_COMMAND"
+    end tell
+end tell
+APPLESCRIPT
+    RESULT=$?
+
+    # osascript returns as soon as the window is open; the run is over, and chained
+    # schedules may start, only once the tool itself exits
+    if [ $RESULT -eq 0 ]; then
+        while [ ! -s "$STATUS_FILE" ] && pgrep -x iTerm2 > /dev/null; do
+            sleep 5
+        done
+        if [ -s "$STATUS_FILE" ]; then
+            RESULT=$(cat "$STATUS_FILE")
+        else
+            echo "=== iTerm quit before $TOOL_NAME finished ===" >> "$ERROR_FILE"
+            RESULT=1
+        fi
+        rm -f "$STATUS_FILE"
+    fi
+else
+    # No iTerm outside macOS: run the tool headless in the target directory through a
+    # login shell, so the user's PATH applies, with its output going to the log
+    (cd "$TARGET_DIRECTORY" && bash -lc "$HEADLESS_COMMAND") >> "$LOG_FILE" 2>> "$ERROR_FILE"
+    RESULT=$?
+fi
+
+if [ $RESULT -eq 0 ]; then
+    echo "=== $TOOL_NAME execution completed successfully at $(date) ===" >> "$LOG_FILE"
+    append_history "success"
+else
+    echo "=== $TOOL_NAME execution failed with error code $RESULT at $(date) ===" >> "$ERROR_FILE"
+    append_history "failure"
+fi
+
+# Start schedules chained after this one
+if [ -n "$SCHEDULER_BIN" ] && [ -x "$SCHEDULER_BIN" ]; then
+    if [ $RESULT -eq 0 ]; then
+        TOOL="$TOOL" "$SCHEDULER_BIN" run-followups success >> "$LOG_FILE" 2>> "$ERROR_FILE"
+    else
+        TOOL="$TOOL" "$SCHEDULER_BIN" run-followups failure >> "$LOG_FILE" 2>> "$ERROR_FILE"
+    fi
+fi
+
+# Auto-delete for 'once' schedule type
+if [ "$SCHEDULE_TYPE" = "once" ]; then
+    echo "=== Auto-deleting one-time schedule ===" >> "$LOG_FILE"
+    unregister_self
+    echo "=== One-time schedule deleted ===" >> "$LOG_FILE"
+else
+    # Unregister once the final allowed run has happened
+    SCHEDULE_FINISHED=0
+    if [ -n "$SCHEDULE_MAX_RUNS" ] && [ "$(count_successes)" -ge "$SCHEDULE_MAX_RUNS" ]; then
+        SCHEDULE_FINISHED=1
+    fi
+    if [ "$TODAY" = "$SCHEDULE_END_DATE" ] && [ -n "$SCHEDULE_TIMES" ]; then
+        LAST_TIME="${SCHEDULE_TIMES##*,}"
+        if [[ ! "$(schedule_date +%H:%M)" < "$LAST_TIME" ]]; then
+            SCHEDULE_FINISHED=1
+        fi
+    fi
+    if [ $SCHEDULE_FINISHED -eq 1 ]; then
+        echo "=== Schedule reached its end date or run limit, unregistering ===" >> "$LOG_FILE"
+        append_history "expired"
+        unregister_self
+    fi
+fi
+
+exit $RESULT
@@ -0,0 +1,477 @@
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::plist_manager;
+
+const CALENDARS_FILE: &str = "blackout-calendars.json";
+/// Yearly events with a COUNT or UNTIL are imported one year at a time, up to this many
+const MAX_YEARLY_OCCURRENCES: u32 = 100;
+
+/// An inclusive range of excluded days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlackoutRange {
+    pub start: String, // YYYY-MM-DD
+    pub end: String,   // YYYY-MM-DD
+    #[serde(default)]
+    pub yearly: bool, // repeat every year; only month and day are compared
+}
+
+/// A named set of days on which schedules referencing it do not run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlackoutCalendar {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub dates: Vec<String>, // YYYY-MM-DD
+    #[serde(default)]
+    pub ranges: Vec<BlackoutRange>,
+}
+
+fn parse_date(date_str: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok()
+}
+
+impl BlackoutRange {
+    fn contains(&self, date: NaiveDate) -> bool {
+        let (Some(start), Some(end)) = (parse_date(&self.start), parse_date(&self.end)) else {
+            return false;
+        };
+        if !self.yearly {
+            return start <= date && date <= end;
+        }
+        // Feb 29 stands in for Feb 28 in common years, so a Feb 29 range still applies
+        let leap = NaiveDate::from_ymd_opt(date.year(), 2, 29).is_some();
+        let month_day = |date: NaiveDate| match (date.month(), date.day()) {
+            (2, 29) if !leap => (2, 28),
+            month_day => month_day,
+        };
+        let day = (date.month(), date.day());
+        let start = month_day(start);
+        let end = month_day(end);
+        if start <= end {
+            start <= day && day <= end
+        } else {
+            // Wraps the new year, e.g. Dec 29 - Jan 3
+            day >= start || day <= end
+        }
+    }
+}
+
+impl BlackoutCalendar {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.dates.iter().any(|d| parse_date(d) == Some(date))
+            || self.ranges.iter().any(|range| range.contains(date))
+    }
+
+    /// Check that every date is YYYY-MM-DD and every range is in order
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("カレンダー名を入力してください".to_string());
+        }
+        for date in &self.dates {
+            if parse_date(date).is_none() {
+                return Err(format!("日付の形式が正しくありません: {}", date));
+            }
+        }
+        for range in &self.ranges {
+            let (Some(start), Some(end)) = (parse_date(&range.start), parse_date(&range.end)) else {
+                return Err(format!("期間の形式が正しくありません: {} - {}", range.start, range.end));
+            };
+            if !range.yearly && start > end {
+                return Err(format!("期間の開始日が終了日より後です: {} - {}", range.start, range.end));
+            }
+            // Yearly ranges compare only month and day, so they must be shorter than a year
+            // (Feb 29 plus a year is Feb 28)
+            if range.yearly && start.checked_add_months(Months::new(12)).is_some_and(|next| end >= next) {
+                return Err(format!("毎年の期間は1年未満で指定してください: {} - {}", range.start, range.end));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn get_calendars_path() -> Result<PathBuf, String> {
+    Ok(plist_manager::ensure_config_dir()?.join(CALENDARS_FILE))
+}
+
+pub fn load_calendars() -> Result<Vec<BlackoutCalendar>, String> {
+    let path = get_calendars_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read blackout calendars: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse blackout calendars: {}", e))
+}
+
+pub fn save_calendars(calendars: &[BlackoutCalendar]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(calendars)
+        .map_err(|e| format!("Failed to serialize blackout calendars: {}", e))?;
+    let path = get_calendars_path()?;
+    // Write a sibling file and rename it, so a crash never leaves truncated calendars
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, content)
+        .map_err(|e| format!("Failed to write blackout calendars: {}", e))?;
+    fs::rename(&temp_path, &path).map_err(|e| format!("Failed to write blackout calendars: {}", e))
+}
+
+/// Whether any of the referenced calendars excludes `date`. Unknown IDs are ignored.
+pub fn is_blacked_out(calendars: &[BlackoutCalendar], calendar_ids: &[String], date: NaiveDate) -> bool {
+    calendars
+        .iter()
+        .filter(|calendar| calendar_ids.contains(&calendar.id))
+        .any(|calendar| calendar.contains(date))
+}
+
+/// Read an all-day iCalendar DTSTART/DTEND value ("20240101"). Timed values such as
+/// "20240101T090000Z" give `None`: their local date depends on the zone.
+fn parse_ics_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    if value.len() != 8 {
+        return None;
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d").ok()
+}
+
+/// A calendar imported from an .ics file, with the events it could not represent
+#[derive(Debug, Clone, Serialize)]
+pub struct IcsImport {
+    pub calendar: BlackoutCalendar,
+    pub skipped: Vec<String>, // SUMMARY (or DTSTART) of each recurring event left out
+}
+
+/// The days an all-day event covers, as a single date or a range
+fn push_event(calendar: &mut BlackoutCalendar, start: NaiveDate, last: NaiveDate, yearly: bool) {
+    if start == last && !yearly {
+        calendar.dates.push(start.format("%Y-%m-%d").to_string());
+    } else {
+        calendar.ranges.push(BlackoutRange {
+            start: start.format("%Y-%m-%d").to_string(),
+            end: last.format("%Y-%m-%d").to_string(),
+            yearly,
+        });
+    }
+}
+
+/// Add an event that repeats by `rrule` (its RRULE parts, upper-cased) to the calendar;
+/// returns false for recurrences that cannot be represented. Only plain FREQ=YEARLY
+/// rules are: without COUNT or UNTIL they become a yearly range, with either they are
+/// expanded year by year, leaving out EXDATE starts.
+fn push_recurring_event(
+    calendar: &mut BlackoutCalendar,
+    start: NaiveDate,
+    last: NaiveDate,
+    rrule: &HashMap<String, String>,
+    exdates: &HashSet<NaiveDate>,
+) -> bool {
+    let supported = rrule.iter().all(|(key, value)| match key.as_str() {
+        "FREQ" => value == "YEARLY",
+        "INTERVAL" => value == "1",
+        "COUNT" | "UNTIL" | "WKST" => true,
+        // BYMONTH, BYDAY and the like move the date from year to year
+        _ => false,
+    });
+    if !supported {
+        return false;
+    }
+
+    let count = match rrule.get("COUNT") {
+        Some(count) => match count.parse::<u32>() {
+            Ok(count) => Some(count),
+            Err(_) => return false,
+        },
+        None => None,
+    };
+    let until = match rrule.get("UNTIL") {
+        Some(until) => match until.get(..8).and_then(parse_ics_date) {
+            Some(until) => Some(until),
+            None => return false,
+        },
+        None => None,
+    };
+    if count.is_none() && until.is_none() {
+        // A range repeating forever cannot leave out single years
+        if !exdates.is_empty() {
+            return false;
+        }
+        push_event(calendar, start, last, true);
+        return true;
+    }
+
+    let length = last - start;
+    let limit = count.unwrap_or(MAX_YEARLY_OCCURRENCES).min(MAX_YEARLY_OCCURRENCES);
+    for year in 0..limit {
+        let Some(occurrence) = start.checked_add_months(Months::new(12 * year)) else {
+            break;
+        };
+        if until.is_some_and(|until| occurrence > until) {
+            break;
+        }
+        if !exdates.contains(&occurrence) {
+            push_event(calendar, occurrence, occurrence + length, false);
+        }
+    }
+    true
+}
+
+/// Build a calendar from the all-day events of an .ics file; timed events are skipped.
+/// Multi-day events become ranges (DTEND is exclusive). FREQ=YEARLY events repeat every
+/// year, or for as long as their COUNT or UNTIL allows; other recurrences are reported
+/// in `skipped` rather than imported as a single day.
+pub fn parse_ics(id: String, name: String, content: &str) -> Result<IcsImport, String> {
+    // Long lines are folded onto continuation lines starting with a space or tab
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match line.strip_prefix([' ', '\t']) {
+            Some(rest) if !lines.is_empty() => lines.last_mut().unwrap().push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut calendar = BlackoutCalendar {
+        id,
+        name,
+        dates: Vec::new(),
+        ranges: Vec::new(),
+    };
+    let mut skipped = Vec::new();
+    let mut in_event = false;
+    let mut start = None;
+    let mut end = None;
+    let mut summary = None;
+    let mut rrule: Option<HashMap<String, String>> = None;
+    let mut exdates = HashSet::new();
+
+    for line in &lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Drop parameters such as DTSTART;VALUE=DATE
+        let key = key.split(';').next().unwrap_or_default().to_ascii_uppercase();
+        match key.as_str() {
+            "BEGIN" if value == "VEVENT" => {
+                in_event = true;
+                start = None;
+                end = None;
+                summary = None;
+                rrule = None;
+                exdates.clear();
+            }
+            "DTSTART" if in_event => start = parse_ics_date(value),
+            "DTEND" if in_event => end = parse_ics_date(value),
+            "SUMMARY" if in_event => summary = Some(value.to_string()),
+            "RRULE" if in_event => {
+                rrule = Some(
+                    value
+                        .to_ascii_uppercase()
+                        .split(';')
+                        .filter_map(|part| part.split_once('='))
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                );
+            }
+            "EXDATE" if in_event => {
+                exdates.extend(
+                    value
+                        .split(',')
+                        .filter_map(|date| date.get(..8).and_then(parse_ics_date)),
+                );
+            }
+            "END" if value == "VEVENT" && in_event => {
+                in_event = false;
+                let Some(start) = start else {
+                    continue;
+                };
+                let last = end
+                    .and_then(|end| end.pred_opt())
+                    .filter(|last| *last > start)
+                    .unwrap_or(start);
+                match &rrule {
+                    None => push_event(&mut calendar, start, last, false),
+                    Some(rrule) => {
+                        if !push_recurring_event(&mut calendar, start, last, rrule, &exdates) {
+                            skipped.push(
+                                summary
+                                    .clone()
+                                    .unwrap_or_else(|| start.format("%Y-%m-%d").to_string()),
+                            );
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if calendar.dates.is_empty() && calendar.ranges.is_empty() {
+        return Err("icsファイルに予定が見つかりませんでした".to_string());
+    }
+    calendar.dates.sort();
+    calendar.dates.dedup();
+    Ok(IcsImport { calendar, skipped })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn ics(events: &[&str]) -> String {
+        let mut lines = vec!["BEGIN:VCALENDAR", "VERSION:2.0"];
+        for event in events {
+            lines.push("BEGIN:VEVENT");
+            lines.extend(event.lines());
+            lines.push("END:VEVENT");
+        }
+        lines.push("END:VCALENDAR");
+        lines.join("\r\n")
+    }
+
+    fn parse(events: &[&str]) -> Result<BlackoutCalendar, String> {
+        import(events).map(|import| import.calendar)
+    }
+
+    fn import(events: &[&str]) -> Result<IcsImport, String> {
+        parse_ics("id".to_string(), "holidays".to_string(), &ics(events))
+    }
+
+    #[test]
+    fn all_day_events_become_dates() {
+        let calendar = parse(&[
+            "DTSTART;VALUE=DATE:20250101\nDTEND;VALUE=DATE:20250102\nSUMMARY:New Year",
+            "DTSTART:20250211",
+        ])
+        .unwrap();
+        assert_eq!(calendar.dates, vec!["2025-01-01", "2025-02-11"]);
+        assert!(calendar.ranges.is_empty());
+    }
+
+    #[test]
+    fn multi_day_events_end_before_dtend() {
+        let calendar = parse(&["DTSTART;VALUE=DATE:20250429\nDTEND;VALUE=DATE:20250506"]).unwrap();
+        assert_eq!(calendar.ranges.len(), 1);
+        assert_eq!(calendar.ranges[0].start, "2025-04-29");
+        assert_eq!(calendar.ranges[0].end, "2025-05-05");
+        assert!(calendar.contains(date(2025, 5, 5)));
+        assert!(!calendar.contains(date(2025, 5, 6)));
+    }
+
+    #[test]
+    fn folded_lines_are_joined() {
+        let calendar = parse(&["SUMMARY:Golden\n  Week\nDTSTART;VALUE=DATE:2025\n 0503\nRRULE:FREQ=YEA\n\tRLY"]).unwrap();
+        assert_eq!(calendar.ranges.len(), 1);
+        assert_eq!(calendar.ranges[0].start, "2025-05-03");
+        assert!(calendar.ranges[0].yearly);
+    }
+
+    #[test]
+    fn yearly_events_repeat() {
+        let calendar = parse(&["DTSTART;VALUE=DATE:20241229\nDTEND;VALUE=DATE:20250104\nRRULE:FREQ=YEARLY"]).unwrap();
+        assert!(calendar.contains(date(2030, 12, 31)));
+        assert!(calendar.contains(date(2031, 1, 3)));
+        assert!(!calendar.contains(date(2031, 1, 4)));
+    }
+
+    #[test]
+    fn yearly_events_stop_at_count_or_until() {
+        let calendar = parse(&["DTSTART;VALUE=DATE:20250101\nRRULE:FREQ=YEARLY;COUNT=3"]).unwrap();
+        assert_eq!(calendar.dates, vec!["2025-01-01", "2026-01-01", "2027-01-01"]);
+        assert!(calendar.ranges.is_empty());
+
+        let calendar = parse(&[
+            "DTSTART;VALUE=DATE:20241229\nDTEND;VALUE=DATE:20250104\nRRULE:FREQ=YEARLY;UNTIL=20251229T000000Z",
+        ])
+        .unwrap();
+        assert_eq!(calendar.ranges.len(), 2);
+        assert!(calendar.ranges.iter().all(|range| !range.yearly));
+        assert!(calendar.contains(date(2026, 1, 3)));
+        assert!(!calendar.contains(date(2026, 12, 31)));
+    }
+
+    #[test]
+    fn exdates_leave_out_years() {
+        let calendar = parse(&[
+            "DTSTART;VALUE=DATE:20250101\nRRULE:FREQ=YEARLY;COUNT=3\nEXDATE;VALUE=DATE:20260101",
+        ])
+        .unwrap();
+        assert_eq!(calendar.dates, vec!["2025-01-01", "2027-01-01"]);
+
+        // A range repeating forever cannot leave a year out
+        let import = import(&[
+            "DTSTART;VALUE=DATE:20250101\nRRULE:FREQ=YEARLY\nEXDATE;VALUE=DATE:20260101\nSUMMARY:New Year",
+            "DTSTART;VALUE=DATE:20250211",
+        ])
+        .unwrap();
+        assert_eq!(import.skipped, vec!["New Year"]);
+        assert_eq!(import.calendar.dates, vec!["2025-02-11"]);
+    }
+
+    #[test]
+    fn unsupported_recurrences_are_reported() {
+        let import = import(&[
+            "DTSTART;VALUE=DATE:20250106\nRRULE:FREQ=WEEKLY;BYDAY=MO\nSUMMARY:Weekly",
+            "DTSTART;VALUE=DATE:20250131\nRRULE:FREQ=MONTHLY",
+            "DTSTART;VALUE=DATE:20250113\nRRULE:FREQ=YEARLY;BYMONTH=1;BYDAY=2MO\nSUMMARY:Coming of Age Day",
+            "DTSTART;VALUE=DATE:20250101\nRRULE:FREQ=YEARLY;INTERVAL=2",
+            "DTSTART;VALUE=DATE:20250211",
+        ])
+        .unwrap();
+        assert_eq!(
+            import.skipped,
+            vec!["Weekly", "2025-01-31", "Coming of Age Day", "2025-01-01"]
+        );
+        assert_eq!(import.calendar.dates, vec!["2025-02-11"]);
+        assert!(import.calendar.ranges.is_empty());
+    }
+
+    #[test]
+    fn timed_events_are_skipped() {
+        let calendar = parse(&[
+            "DTSTART:20250101T090000Z\nDTEND:20250101T100000Z",
+            "DTSTART;TZID=Asia/Tokyo:20250102T090000",
+            "DTSTART;VALUE=DATE:20250103",
+        ])
+        .unwrap();
+        assert_eq!(calendar.dates, vec!["2025-01-03"]);
+        assert!(parse(&["DTSTART:20250101T090000Z"]).is_err());
+    }
+
+    #[test]
+    fn yearly_feb_29_falls_on_feb_28_in_common_years() {
+        let range = BlackoutRange {
+            start: "2024-02-29".to_string(),
+            end: "2024-02-29".to_string(),
+            yearly: true,
+        };
+        assert!(range.contains(date(2028, 2, 29)));
+        assert!(!range.contains(date(2028, 2, 28)));
+        assert!(range.contains(date(2025, 2, 28)));
+        assert!(!range.contains(date(2025, 3, 1)));
+    }
+
+    #[test]
+    fn yearly_ranges_must_be_shorter_than_a_year() {
+        let calendar = |start: &str, end: &str| BlackoutCalendar {
+            id: "id".to_string(),
+            name: "holidays".to_string(),
+            dates: Vec::new(),
+            ranges: vec![BlackoutRange {
+                start: start.to_string(),
+                end: end.to_string(),
+                yearly: true,
+            }],
+        };
+        assert!(calendar("2024-12-29", "2025-01-03").validate().is_ok());
+        assert!(calendar("2024-01-01", "2024-12-31").validate().is_ok());
+        assert!(calendar("2024-01-01", "2025-03-01").validate().is_err());
+        assert!(calendar("2024-02-29", "2025-02-28").validate().is_err());
+        assert!(calendar("2024-02-29", "2025-02-27").validate().is_ok());
+        assert!(calendar("2025-02-29", "2025-03-01").validate().is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::occurrence;
+use crate::plist_manager::RegisteredSchedule;
+
+/// How many days ahead registrations are compared against each other
+const CONFLICT_HORIZON_DAYS: i64 = 14;
+
+/// Another schedule that fires close to one being registered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleConflict {
+    pub schedule_id: String,
+    pub title: String,
+    pub tool: String,
+    pub reason: String,             // "same_tool", "same_directory", "same_tool_and_directory"
+    pub scheduled_at: String,       // first clashing run of the new schedule (RFC 3339)
+    pub other_scheduled_at: String, // the other schedule's run it clashes with (RFC 3339)
+}
+
+impl ScheduleConflict {
+    /// One-line Japanese description for the result message
+    pub fn describe(&self) -> String {
+        let reason = match self.reason.as_str() {
+            "same_tool" => "同じツール",
+            "same_directory" => "同じディレクトリ",
+            _ => "同じツール・ディレクトリ",
+        };
+        format!("「{}」と実行時刻が近接しています（{}）", self.title, reason)
+    }
+}
+
+/// First pair of runs (one from each list, both ascending) at most `window` apart
+fn first_clash(
+    ours: &[DateTime<Utc>],
+    theirs: &[DateTime<Utc>],
+    window: chrono::Duration,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let (mut i, mut j) = (0, 0);
+    while i < ours.len() && j < theirs.len() {
+        if (ours[i] - theirs[j]).abs() <= window {
+            return Some((ours[i], theirs[j]));
+        }
+        if ours[i] < theirs[j] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    None
+}
+
+/// Schedules among `others` sharing the tool or target directory with `schedule` whose
+/// runs over the next two weeks come within `window_minutes` of its runs
+pub fn find_conflicts(
+    schedule: &RegisteredSchedule,
+    others: &[RegisteredSchedule],
+    window_minutes: u32,
+    now: DateTime<Utc>,
+) -> Vec<ScheduleConflict> {
+    let horizon = now + chrono::Duration::days(CONFLICT_HORIZON_DAYS);
+    let runs = |schedule: &RegisteredSchedule| -> Vec<DateTime<Utc>> {
+        occurrence::scheduled_between_in_zone(schedule, now, horizon)
+            .into_iter()
+            .map(|time| time.with_timezone(&Utc))
+            .collect()
+    };
+    let ours = runs(schedule);
+    if ours.is_empty() {
+        return Vec::new();
+    }
+    // A jittered run may start anywhere inside its jitter window
+    let window = chrono::Duration::minutes(
+        (window_minutes + schedule.jitter_minutes.unwrap_or(0)) as i64,
+    );
+    let directory = schedule.target_directory.trim_end_matches('/');
+
+    let mut conflicts = Vec::new();
+    for other in others {
+        if other.schedule_id == schedule.schedule_id || !other.enabled {
+            continue;
+        }
+        let same_tool = other.tool == schedule.tool;
+        let same_directory =
+            !directory.is_empty() && other.target_directory.trim_end_matches('/') == directory;
+        let reason = match (same_tool, same_directory) {
+            (true, true) => "same_tool_and_directory",
+            (true, false) => "same_tool",
+            (false, true) => "same_directory",
+            (false, false) => continue,
+        };
+        let window = window + chrono::Duration::minutes(other.jitter_minutes.unwrap_or(0) as i64);
+        if let Some((ours_at, theirs_at)) = first_clash(&ours, &runs(other), window) {
+            conflicts.push(ScheduleConflict {
+                schedule_id: other.schedule_id.clone(),
+                title: other.title.clone(),
+                tool: other.tool.clone(),
+                reason: reason.to_string(),
+                scheduled_at: ours_at.to_rfc3339(),
+                other_scheduled_at: theirs_at.to_rfc3339(),
+            });
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(schedule_id: &str, tool: &str, directory: &str, time: &str) -> RegisteredSchedule {
+        RegisteredSchedule {
+            tool: tool.to_string(),
+            schedule_id: schedule_id.to_string(),
+            title: schedule_id.to_string(),
+            execution_time: vec![time.to_string()],
+            target_directory: directory.to_string(),
+            command_args: String::new(),
+            created_at: String::new(),
+            schedule_type: "daily".to_string(),
+            interval_value: None,
+            start_date: None,
+            weekdays: None,
+            month_day: None,
+            month_week: None,
+            cron_expression: None,
+            end_date: None,
+            max_runs: None,
+            interval_unit: None,
+            active_start: None,
+            active_end: None,
+            timezone: None,
+            blackout_calendars: None,
+            jitter_minutes: None,
+            enabled: true,
+            after_tool: None,
+            after_schedule_id: None,
+            after_status: None,
+            updated_at: None,
+            last_modified_by: None,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap()
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 10, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn first_clash_finds_the_earliest_pair_within_the_window() {
+        let ours = [at(9, 0), at(12, 0)];
+        let theirs = [at(10, 0), at(12, 20)];
+        let window = chrono::Duration::minutes(30);
+        assert_eq!(first_clash(&ours, &theirs, window), Some((at(12, 0), at(12, 20))));
+        assert_eq!(first_clash(&ours, &theirs, chrono::Duration::minutes(10)), None);
+        assert_eq!(first_clash(&ours, &[], window), None);
+    }
+
+    #[test]
+    fn reports_the_shared_tool_or_directory() {
+        let ours = schedule("new", "claude", "/work/app", "09:00");
+        let others = [
+            schedule("same-tool", "claude", "/work/other", "09:10"),
+            schedule("same-directory", "codex", "/work/app/", "09:10"),
+            schedule("both", "claude", "/work/app", "08:50"),
+            schedule("unrelated", "gemini", "/work/other", "09:00"),
+        ];
+        let conflicts = find_conflicts(&ours, &others, 15, now());
+        let reasons: Vec<(&str, &str)> = conflicts
+            .iter()
+            .map(|conflict| (conflict.schedule_id.as_str(), conflict.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("same-tool", "same_tool"),
+                ("same-directory", "same_directory"),
+                ("both", "same_tool_and_directory"),
+            ]
+        );
+    }
+
+    #[test]
+    fn an_empty_directory_is_not_shared() {
+        let ours = schedule("new", "claude", "", "09:00");
+        let others = [schedule("other", "codex", "", "09:00")];
+        assert!(find_conflicts(&ours, &others, 15, now()).is_empty());
+    }
+
+    #[test]
+    fn jitter_widens_the_window() {
+        let ours = schedule("new", "claude", "/work/app", "09:00");
+        let other = schedule("other", "claude", "/work/other", "09:40");
+        assert!(find_conflicts(&ours, std::slice::from_ref(&other), 15, now()).is_empty());
+
+        let jittered = RegisteredSchedule { jitter_minutes: Some(30), ..ours.clone() };
+        assert_eq!(find_conflicts(&jittered, std::slice::from_ref(&other), 15, now()).len(), 1);
+        let other = RegisteredSchedule { jitter_minutes: Some(25), ..other };
+        assert_eq!(find_conflicts(&ours, &[other], 15, now()).len(), 1);
+    }
+
+    #[test]
+    fn skips_itself_and_paused_schedules() {
+        // An update compares the edited schedule against the stored copy of itself
+        let ours = schedule("edited", "claude", "/work/app", "09:00");
+        let stored = schedule("edited", "claude", "/work/app", "09:05");
+        let paused = RegisteredSchedule {
+            enabled: false,
+            ..schedule("paused", "claude", "/work/app", "09:00")
+        };
+        assert!(find_conflicts(&ours, &[stored, paused], 15, now()).is_empty());
+    }
+}
@@ -0,0 +1,367 @@
+use chrono::{Datelike, NaiveDate, NaiveTime};
+
+use crate::plist_manager::CalendarInterval;
+
+/// Upper bound on the number of StartCalendarInterval entries a schedule may expand to
+pub const MAX_CALENDAR_ENTRIES: usize = 1000;
+
+/// How far back to look for a matching day (covers Feb 29 schedules)
+pub const MAX_LOOKBACK_DAYS: u32 = 366 * 4 + 1;
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A parsed five-field cron expression: minute hour day-of-month month day-of-week.
+/// A field of `None` is a plain `*` wildcard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: Option<Vec<u32>>,
+    hours: Option<Vec<u32>>,
+    days: Option<Vec<u32>>,
+    months: Option<Vec<u32>>,
+    weekdays: Option<Vec<u32>>, // 0 = Sun, 1 = Mon, ...
+    // Like Vixie cron, the day fields are ORed only when neither starts with `*`;
+    // `0 9 */2 * 1` runs on odd days that are also Mondays
+    days_or_weekdays: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "cron expression must have 5 fields, got {}",
+                fields.len()
+            ));
+        }
+
+        let minutes = parse_field(fields[0], 0, 59, &[], 0)?;
+        let hours = parse_field(fields[1], 0, 23, &[], 0)?;
+        let days = parse_field(fields[2], 1, 31, &[], 0)?;
+        let months = parse_field(fields[3], 1, 12, &MONTH_NAMES, 1)?;
+        // Day-of-week accepts 0-7 where both 0 and 7 mean Sunday
+        let weekdays = parse_field(fields[4], 0, 7, &WEEKDAY_NAMES, 0)?.map(|values| {
+            let mut normalized: Vec<u32> = values.into_iter().map(|v| v % 7).collect();
+            normalized.sort_unstable();
+            normalized.dedup();
+            normalized
+        });
+
+        Ok(CronSchedule {
+            minutes,
+            hours,
+            days,
+            months,
+            weekdays,
+            days_or_weekdays: !fields[2].starts_with('*') && !fields[4].starts_with('*'),
+        })
+    }
+
+    /// Expand into launchd calendar entries. Omitted keys act as wildcards, matching cron.
+    pub fn calendar_intervals(&self) -> Result<Vec<CalendarInterval>, String> {
+        // cron fires when either day-of-month or day-of-week matches if both are restricted,
+        // so emit the two sets as separate entries instead of combining them in one dict.
+        // launchd cannot AND the two, so a `*`-prefixed day field fires on the smaller set
+        // and `check-due` drops the days the other field excludes.
+        let mut day_selectors: Vec<(Option<u32>, Option<u32>)> = Vec::new();
+        match (&self.days, &self.weekdays) {
+            (Some(days), Some(weekdays)) if self.days_or_weekdays => {
+                day_selectors.extend(days.iter().map(|d| (Some(*d), None)));
+                day_selectors.extend(weekdays.iter().map(|w| (None, Some(*w))));
+            }
+            (Some(days), Some(weekdays)) if days.len() < weekdays.len() => {
+                day_selectors.extend(days.iter().map(|d| (Some(*d), None)))
+            }
+            (Some(_), Some(weekdays)) => {
+                day_selectors.extend(weekdays.iter().map(|w| (None, Some(*w))))
+            }
+            (Some(days), None) => day_selectors.extend(days.iter().map(|d| (Some(*d), None))),
+            (None, Some(weekdays)) => {
+                day_selectors.extend(weekdays.iter().map(|w| (None, Some(*w))))
+            }
+            (None, None) => day_selectors.push((None, None)),
+        }
+
+        let months = optional_values(&self.months);
+        let hours = optional_values(&self.hours);
+        let minutes = optional_values(&self.minutes);
+
+        let total = months.len() * day_selectors.len() * hours.len() * minutes.len();
+        if total > MAX_CALENDAR_ENTRIES {
+            return Err(format!(
+                "cron expression expands to {} calendar entries (max {})",
+                total, MAX_CALENDAR_ENTRIES
+            ));
+        }
+
+        let mut entries = Vec::with_capacity(total);
+        for month in &months {
+            for (day, weekday) in &day_selectors {
+                for hour in &hours {
+                    for minute in &minutes {
+                        entries.push(CalendarInterval {
+                            minute: *minute,
+                            hour: *hour,
+                            day: *day,
+                            weekday: *weekday,
+                            month: *month,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Whether the expression fires on the given date (at any time of day)
+    pub fn matches_date(&self, date: NaiveDate) -> bool {
+        if let Some(months) = &self.months {
+            if !months.contains(&date.month()) {
+                return false;
+            }
+        }
+        let day_match = self
+            .days
+            .as_ref()
+            .map(|days| days.contains(&date.day()));
+        let weekday_match = self
+            .weekdays
+            .as_ref()
+            .map(|weekdays| weekdays.contains(&date.weekday().num_days_from_sunday()));
+        match (day_match, weekday_match) {
+            (Some(d), Some(w)) if self.days_or_weekdays => d || w,
+            (Some(d), Some(w)) => d && w,
+            (Some(d), None) => d,
+            (None, Some(w)) => w,
+            (None, None) => true,
+        }
+    }
+
+    /// The five fields as plain numeric lists, which every cron implementation reads the
+    /// same way. Day fields restricted through `*` (like `*/2`) lose their star, so cron
+    /// ORs them with the weekdays and may fire on days `matches_date` rejects; check-due
+    /// in the run script skips those.
+    pub fn numeric_fields(&self) -> String {
+        let field = |values: &Option<Vec<u32>>| match values {
+            Some(values) => values.iter().map(u32::to_string).collect::<Vec<_>>().join(","),
+            None => "*".to_string(),
+        };
+        [&self.minutes, &self.hours, &self.days, &self.months, &self.weekdays]
+            .map(field)
+            .join(" ")
+    }
+
+    /// All times of day the expression fires at, in ascending order
+    pub fn times(&self) -> Vec<NaiveTime> {
+        let hours = self.hours.clone().unwrap_or_else(|| (0..24).collect());
+        let minutes = self.minutes.clone().unwrap_or_else(|| (0..60).collect());
+        let mut times = Vec::with_capacity(hours.len() * minutes.len());
+        for hour in &hours {
+            for minute in &minutes {
+                if let Some(time) = NaiveTime::from_hms_opt(*hour, *minute, 0) {
+                    times.push(time);
+                }
+            }
+        }
+        times
+    }
+}
+
+fn optional_values(values: &Option<Vec<u32>>) -> Vec<Option<u32>> {
+    match values {
+        Some(values) => values.iter().map(|v| Some(*v)).collect(),
+        None => vec![None],
+    }
+}
+
+fn parse_value(token: &str, names: &[&str], name_offset: u32) -> Result<u32, String> {
+    if let Ok(value) = token.parse::<u32>() {
+        return Ok(value);
+    }
+    let upper = token.to_ascii_uppercase();
+    names
+        .iter()
+        .position(|name| *name == upper)
+        .map(|index| index as u32 + name_offset)
+        .ok_or_else(|| format!("invalid cron value: {}", token))
+}
+
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    name_offset: u32,
+) -> Result<Option<Vec<u32>>, String> {
+    if field == "*" {
+        return Ok(None);
+    }
+
+    let mut values = Vec::new();
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("invalid cron step: {}", item))?;
+                if step == 0 {
+                    return Err(format!("invalid cron step: {}", item));
+                }
+                (range, step)
+            }
+            None => (item, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, names, name_offset)?,
+                parse_value(end, names, name_offset)?,
+            )
+        } else {
+            let value = parse_value(range, names, name_offset)?;
+            // "5/15" means "from 5 to the end of the range, every 15"
+            if step > 1 {
+                (value, max)
+            } else {
+                (value, value)
+            }
+        };
+
+        if start < min || end > max || start > end {
+            return Err(format!(
+                "cron value out of range ({}-{}): {}",
+                min, max, item
+            ));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Ok(Some(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_fields() {
+        let cases: [(&str, usize, Vec<u32>); 7] = [
+            ("*/15 * * * *", 0, vec![0, 15, 30, 45]),
+            ("5/20 * * * *", 0, vec![5, 25, 45]),
+            ("0 9-17/4 * * *", 1, vec![9, 13, 17]),
+            ("0 9 1,15,10-12 * *", 2, vec![1, 10, 11, 12, 15]),
+            ("0 9 * jan,Mar-MAY *", 3, vec![1, 3, 4, 5]),
+            ("0 9 * * MON-fri", 4, vec![1, 2, 3, 4, 5]),
+            ("0 9 * * 5-7", 4, vec![0, 5, 6]),
+        ];
+        for (expression, field, expected) in cases {
+            let cron = CronSchedule::parse(expression).unwrap();
+            let values = [&cron.minutes, &cron.hours, &cron.days, &cron.months, &cron.weekdays][field];
+            assert_eq!(values.as_ref(), Some(&expected), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "0 24 * * *",
+            "0 9 0 * *",
+            "0 9 * 13 *",
+            "0 9 * * 8",
+            "*/0 * * * *",
+            "0 9 5-1 * *",
+            "0 9 1,15,L * *",
+        ] {
+            assert!(CronSchedule::parse(expression).is_err(), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn sunday_is_zero_or_seven() {
+        assert_eq!(
+            CronSchedule::parse("0 9 * * 7").unwrap(),
+            CronSchedule::parse("0 9 * * 0").unwrap()
+        );
+        assert!(CronSchedule::parse("0 9 * * 7").unwrap().matches_date(date(2025, 3, 9)));
+    }
+
+    #[test]
+    fn restricted_day_fields_are_ored() {
+        // The 1st or any Monday
+        let cron = CronSchedule::parse("0 9 1 * MON").unwrap();
+        assert!(cron.matches_date(date(2025, 3, 1))); // Saturday the 1st
+        assert!(cron.matches_date(date(2025, 3, 10))); // Monday
+        assert!(!cron.matches_date(date(2025, 3, 11)));
+
+        let entries = cron.calendar_intervals().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].day, entries[0].weekday), (Some(1), None));
+        assert_eq!((entries[1].day, entries[1].weekday), (None, Some(1)));
+    }
+
+    #[test]
+    fn star_prefixed_day_fields_are_anded() {
+        // Odd days that are also Mondays
+        let cron = CronSchedule::parse("0 9 */2 * 1").unwrap();
+        assert!(cron.matches_date(date(2025, 3, 17)));
+        assert!(!cron.matches_date(date(2025, 3, 10)));
+        assert!(!cron.matches_date(date(2025, 3, 15)));
+
+        // launchd fires every Monday and check-due filters the even days
+        let entries = cron.calendar_intervals().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].day, entries[0].weekday), (None, Some(1)));
+
+        // The 1st when it falls on Sun/Tue/Thu/Sat
+        let cron = CronSchedule::parse("0 9 1 * */2").unwrap();
+        assert!(cron.matches_date(date(2025, 3, 1))); // Saturday
+        assert!(!cron.matches_date(date(2025, 1, 1))); // Wednesday
+        assert!(!cron.matches_date(date(2025, 3, 8))); // Saturday the 8th
+    }
+
+    #[test]
+    fn calendar_intervals_expand_every_field() {
+        let cron = CronSchedule::parse("0,30 9 * JAN,JUL 1-5").unwrap();
+        let entries = cron.calendar_intervals().unwrap();
+        assert_eq!(entries.len(), 2 * 5 * 2);
+        assert!(entries.iter().all(|entry| entry.hour == Some(9) && entry.day.is_none()));
+
+        let entries = CronSchedule::parse("* * * * *").unwrap().calendar_intervals().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].minute, None);
+    }
+
+    #[test]
+    fn numeric_fields_drop_names_and_steps() {
+        let numeric = |expression: &str| CronSchedule::parse(expression).unwrap().numeric_fields();
+        assert_eq!(numeric("0 9 * * MON-fri"), "0 9 * * 1,2,3,4,5");
+        assert_eq!(numeric("5/15 */6 1 jan,Jul 7"), "5,20,35,50 0,6,12,18 1 1,7 0");
+        assert_eq!(numeric("* * * * *"), "* * * * *");
+        // `*/10` loses its star, so cron ORs these days with Mondays
+        assert_eq!(numeric("0 9 */10 * 1"), "0 9 1,11,21,31 * 1");
+    }
+
+    #[test]
+    fn calendar_intervals_are_capped() {
+        // 60 explicit minutes x 17 hours = 1020 entries
+        let cron = CronSchedule::parse("0-59 0-16 * * *").unwrap();
+        let err = cron.calendar_intervals().unwrap_err();
+        assert!(err.contains("1020"), "{}", err);
+        // A plain `*` stays a single wildcard key
+        let entries = CronSchedule::parse("* 0-16 * * *").unwrap().calendar_intervals().unwrap();
+        assert_eq!(entries.len(), 17);
+        assert!(entries.len() <= MAX_CALENDAR_ENTRIES);
+    }
+}
@@ -0,0 +1,701 @@
+use crate::plist_manager::{self, CalendarInterval, LaunchdConfig, RegisteredSchedule};
+use crate::scheduler::{drift_entries, job_drift, ScheduleDrift, SchedulerBackend, SCHEDULER_BIN_ENV};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Schedules registered in a managed block of the user's crontab. Each schedule has an
+/// env file in the config directory naming it, which its crontab line sources before
+/// running the tool's script.
+pub struct CrontabBackend;
+
+const BLOCK_BEGIN: &str = "# BEGIN tauri-cli-scheduler (managed block, do not edit)";
+const BLOCK_END: &str = "# END tauri-cli-scheduler";
+/// Ends every line of a schedule in the block, e.g. "#job=claude:20250101090000000"
+const JOB_TAG: &str = "#job=";
+/// Placeholder lines keeping paused and chained schedules listed without a trigger
+const PAUSED_LINE: &str = "# paused";
+const CHAIN_LINE: &str = "# chain";
+
+/// Directory holding the env file of each schedule
+fn get_env_dir() -> Result<PathBuf, String> {
+    Ok(plist_manager::ensure_config_dir()?.join("crontab"))
+}
+
+fn get_env_path(tool: &str, schedule_id: &str) -> Result<PathBuf, String> {
+    Ok(get_env_dir()?.join(format!("{}.{}.env", tool, schedule_id)))
+}
+
+/// Tool and schedule ID of every env file in `dir`
+fn env_files_in(dir: &Path) -> Result<BTreeSet<(String, String)>, String> {
+    if !dir.exists() {
+        return Ok(BTreeSet::new());
+    }
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read crontab env directory: {}", e))?;
+    let mut files = BTreeSet::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read env file entry: {}", e))?;
+        let file_name = entry.file_name();
+        let Some((tool, schedule_id)) = file_name
+            .to_str()
+            .and_then(|name| name.strip_suffix(".env"))
+            .and_then(|name| name.split_once('.'))
+        else {
+            continue;
+        };
+        files.insert((tool.to_string(), schedule_id.to_string()));
+    }
+    Ok(files)
+}
+
+fn job_tag(tool: &str, schedule_id: &str) -> String {
+    format!("{}{}:{}", JOB_TAG, tool, schedule_id)
+}
+
+fn parse_job_tag(line: &str) -> Option<(String, String)> {
+    let (_, tag) = line.rsplit_once(JOB_TAG)?;
+    let (tool, schedule_id) = tag.trim().split_once(':')?;
+    Some((tool.to_string(), schedule_id.to_string()))
+}
+
+/// Single-quoted word for /bin/sh
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Read a word written by `shell_quote` from the start of `input`; returns the value and
+/// the remaining input
+fn read_shell_quoted(input: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut rest = input;
+    loop {
+        let inner = rest.strip_prefix('\'')?;
+        let end = inner.find('\'')?;
+        value.push_str(&inner[..end]);
+        rest = &inner[end + 1..];
+        match rest.strip_prefix("\\'") {
+            Some(next) => {
+                value.push('\'');
+                rest = next;
+            }
+            None => return Some((value, rest)),
+        }
+    }
+}
+
+/// Parse the `export KEY='value'` lines of an env file
+fn parse_env_file(content: &str) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("export ") {
+        rest = &rest[start + "export ".len()..];
+        let Some((key, quoted)) = rest.split_once('=') else {
+            break;
+        };
+        let Some((value, remaining)) = read_shell_quoted(quoted) else {
+            break;
+        };
+        env.insert(key.trim().to_string(), value);
+        rest = remaining;
+    }
+    env
+}
+
+/// Current crontab, empty when the user has none
+fn read_crontab() -> Result<String, String> {
+    let output = Command::new("crontab")
+        .arg("-l")
+        .output()
+        .map_err(|e| format!("Failed to run crontab -l: {}", e))?;
+
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("no crontab") {
+        Ok(String::new())
+    } else {
+        Err(format!("crontab -l failed: {}", stderr.trim()))
+    }
+}
+
+fn write_crontab(content: &str) -> Result<(), String> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run crontab: {}", e))?;
+    child
+        .stdin
+        .take()
+        .ok_or("Failed to open crontab input".to_string())?
+        .write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write crontab: {}", e))?;
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run crontab: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("crontab failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+/// Lines inside the managed block
+fn managed_lines(crontab: &str) -> Vec<String> {
+    crontab
+        .lines()
+        .skip_while(|line| line.trim() != BLOCK_BEGIN)
+        .skip(1)
+        .take_while(|line| line.trim() != BLOCK_END)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Replace a schedule's lines in the managed block, leaving the rest of the crontab as is
+fn set_schedule_lines(tool: &str, schedule_id: &str, lines: Vec<String>) -> Result<(), String> {
+    let crontab = read_crontab()?;
+    write_crontab(&with_schedule_lines(&crontab, tool, schedule_id, lines))
+}
+
+/// `crontab` with a schedule's lines in the managed block replaced by `lines`. Lines
+/// outside the block are kept, and the block moves to the end or goes away when empty.
+fn with_schedule_lines(crontab: &str, tool: &str, schedule_id: &str, lines: Vec<String>) -> String {
+    let tag = job_tag(tool, schedule_id);
+    let mut block: Vec<String> = managed_lines(crontab)
+        .into_iter()
+        .filter(|line| !line.ends_with(&tag))
+        .collect();
+    block.extend(lines);
+
+    let mut content = Vec::new();
+    let mut in_block = false;
+    for line in crontab.lines() {
+        if line.trim() == BLOCK_BEGIN {
+            in_block = true;
+        } else if in_block {
+            in_block = line.trim() != BLOCK_END;
+        } else {
+            content.push(line.to_string());
+        }
+    }
+    if !block.is_empty() {
+        content.push(BLOCK_BEGIN.to_string());
+        content.extend(block);
+        content.push(BLOCK_END.to_string());
+    }
+    // crontab rejects a last line without a newline
+    format!("{}\n", content.join("\n"))
+}
+
+/// Shell command a schedule's crontab line runs
+fn job_command(tool: &str, schedule_id: &str) -> Result<String, String> {
+    let env_path = get_env_path(tool, schedule_id)?;
+    let script_path = plist_manager::run_script_path(tool)?;
+    let log_dir = plist_manager::ensure_config_dir()?.join("logs");
+    Ok(format!(
+        ". {} && /bin/bash {} >> {} 2>> {}",
+        shell_quote(&env_path.to_string_lossy()),
+        shell_quote(&script_path.to_string_lossy()),
+        shell_quote(&log_dir.join(format!("{}.log", tool)).to_string_lossy()),
+        shell_quote(&log_dir.join(format!("{}.error.log", tool)).to_string_lossy())
+    ))
+}
+
+/// Minute/hour/day/month/weekday fields of a schedule's crontab lines; `None` for chained
+/// schedules, which have no trigger of their own and are started by the upstream run
+fn cron_fields(config: &LaunchdConfig) -> Result<Option<Vec<String>>, String> {
+    if config.schedule_type == "chain" {
+        return Ok(None);
+    }
+    let sub_daily_minutes = if config.schedule_type == "interval" {
+        plist_manager::sub_daily_interval_minutes(&config.interval_unit, config.interval_value)
+    } else {
+        None
+    };
+    if let Some(minutes) = sub_daily_minutes {
+        // cron steps restart every hour/day, so only even divisions keep a fixed interval;
+        // the active window is checked in the script
+        return match minutes {
+            0 => Err("Interval must be at least one minute".to_string()),
+            minutes if minutes < 60 && 60 % minutes == 0 => Ok(Some(vec![format!("*/{} * * * *", minutes)])),
+            minutes if minutes % 60 == 0 && 24 % (minutes / 60) == 0 => {
+                Ok(Some(vec![format!("0 */{} * * *", minutes / 60)]))
+            }
+            minutes => Err(format!(
+                "crontab can only repeat at intervals that divide an hour or a day evenly (got {} minutes)",
+                minutes
+            )),
+        };
+    }
+
+    // crontab runs in the system zone just like launchd, so unzoned cron expressions can
+    // be used as they are; written as numbers, since names and steps without a range are
+    // not read the same way by every cron
+    if config.schedule_type == "cron" && crate::occurrence::parse_timezone(&config.timezone).is_none() {
+        let expression = config
+            .cron_expression
+            .as_ref()
+            .ok_or("cron expression is required for cron schedules".to_string())?;
+        return Ok(Some(vec![crate::cron::CronSchedule::parse(expression)?.numeric_fields()]));
+    }
+
+    // Neighbouring entries that differ only in the hour share a line, so a schedule with
+    // several times usually needs just one
+    let mut groups: Vec<(CalendarInterval, Vec<u32>)> = Vec::new();
+    for entry in plist_manager::calendar_intervals(config)? {
+        let key = CalendarInterval { hour: None, ..entry.clone() };
+        match (groups.last_mut(), entry.hour) {
+            (Some((last, hours)), Some(hour)) if *last == key && !hours.is_empty() => hours.push(hour),
+            _ => groups.push((key, entry.hour.into_iter().collect())),
+        }
+    }
+    let field = |value: Option<u32>| value.map_or("*".to_string(), |value| value.to_string());
+    Ok(Some(
+        groups
+            .iter()
+            .map(|(entry, hours)| {
+                let hours = if hours.is_empty() {
+                    "*".to_string()
+                } else {
+                    hours.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+                };
+                format!(
+                    "{} {} {} {} {}",
+                    field(entry.minute),
+                    hours,
+                    field(entry.day),
+                    field(entry.month),
+                    field(entry.weekday)
+                )
+            })
+            .collect(),
+    ))
+}
+
+/// Calendar entries of a crontab line written by `cron_fields`
+fn parse_cron_fields(line: &str) -> Option<Vec<CalendarInterval>> {
+    let fields: Vec<&str> = line.split_whitespace().take(5).collect();
+    let &[minute, hours, day, month, weekday] = fields.as_slice() else {
+        return None;
+    };
+    let field = |value: &str| -> Option<Option<u32>> {
+        if value == "*" {
+            Some(None)
+        } else {
+            value.parse::<u32>().ok().map(Some)
+        }
+    };
+    let hours: Vec<Option<u32>> = if hours == "*" {
+        vec![None]
+    } else {
+        hours
+            .split(',')
+            .map(|hour| hour.parse::<u32>().ok().map(Some))
+            .collect::<Option<_>>()?
+    };
+    let (minute, day, month, weekday) = (field(minute)?, field(day)?, field(month)?, field(weekday)?);
+    Some(
+        hours
+            .into_iter()
+            .map(|hour| CalendarInterval {
+                minute,
+                hour,
+                day,
+                weekday,
+                month,
+            })
+            .collect(),
+    )
+}
+
+/// Content of a schedule's env file
+fn env_file(config: &LaunchdConfig) -> Result<String, String> {
+    let env_path = get_env_path(&config.tool, &config.schedule_id)?;
+    let mut env = plist_manager::job_environment(config, CrontabBackend.name());
+    // Lets the run script remove the file when the schedule unregisters itself
+    env.push(("SCHEDULE_ENV_FILE".to_string(), env_path.to_string_lossy().to_string()));
+    Ok(format!(
+        "# Generated by tauri-cli-scheduler, sourced by the schedule's crontab line\n{}",
+        plist_manager::shell_exports(&env)
+    ))
+}
+
+/// An env file without the export that depends on where the app is installed
+fn portable_env_file(content: &str) -> String {
+    let scheduler_bin = format!("export {}=", SCHEDULER_BIN_ENV);
+    content
+        .lines()
+        .filter(|line| !line.starts_with(&scheduler_bin))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// crontab lines of a schedule, a paused placeholder while it is paused
+fn schedule_lines(config: &LaunchdConfig) -> Result<Vec<String>, String> {
+    if config.enabled {
+        job_lines(config)
+    } else {
+        Ok(vec![format!("{} {}", PAUSED_LINE, job_tag(&config.tool, &config.schedule_id))])
+    }
+}
+
+/// crontab lines without the run script's path, which depends on where the app is installed
+fn portable_lines(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            let Some((head, rest)) = line.split_once(" && /bin/bash ") else {
+                return line.clone();
+            };
+            match rest.split_once(" >> ") {
+                Some((_, tail)) => format!("{} && >> {}", head, tail),
+                None => line.clone(),
+            }
+        })
+        .collect()
+}
+
+/// crontab lines of an enabled schedule
+fn job_lines(config: &LaunchdConfig) -> Result<Vec<String>, String> {
+    let tag = job_tag(&config.tool, &config.schedule_id);
+    let Some(fields) = cron_fields(config)? else {
+        return Ok(vec![format!("{} {}", CHAIN_LINE, tag)]);
+    };
+    // `%` ends the command in crontab
+    let command = job_command(&config.tool, &config.schedule_id)?.replace('%', "\\%");
+    Ok(fields
+        .into_iter()
+        .map(|fields| format!("{} {} {}", fields, command, tag))
+        .collect())
+}
+
+/// Load a schedule from its env file and its lines in the managed block, for env files
+/// written before the schedule store
+fn load_schedule(tool: &str, schedule_id: &str, block: &[String]) -> Result<Option<RegisteredSchedule>, String> {
+    let env_path = get_env_path(tool, schedule_id)?;
+    if !env_path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&env_path)
+        .map_err(|e| format!("Failed to read schedule env file: {}", e))?;
+    let tag = job_tag(tool, schedule_id);
+    let paused = block
+        .iter()
+        .any(|line| line.starts_with(PAUSED_LINE) && line.ends_with(&tag));
+
+    Ok(plist_manager::schedule_from_environment(
+        &parse_env_file(&content),
+        Some((tool.to_string(), schedule_id.to_string())),
+        !paused,
+    ))
+}
+
+impl SchedulerBackend for CrontabBackend {
+    fn name(&self) -> &'static str {
+        "crontab"
+    }
+
+    /// The env file and the crontab are written in place; a failed install is undone by
+    /// `create_schedule` rewriting the previous definition
+    fn write_job(&self, config: &LaunchdConfig) -> Result<String, String> {
+        let lines = schedule_lines(config)?;
+
+        let env_dir = get_env_dir()?;
+        fs::create_dir_all(&env_dir)
+            .map_err(|e| format!("Failed to create crontab env directory: {}", e))?;
+        let env_path = get_env_path(&config.tool, &config.schedule_id)?;
+        fs::write(&env_path, env_file(config)?)
+            .map_err(|e| format!("Failed to write schedule env file: {}", e))?;
+
+        set_schedule_lines(&config.tool, &config.schedule_id, lines)?;
+
+        if config.enabled {
+            Ok(format!("Env file created at: {} and added to crontab", env_path.display()))
+        } else {
+            Ok(format!("Env file saved at: {} (paused)", env_path.display()))
+        }
+    }
+
+    fn remove_job(&self, tool: &str, schedule_id: &str) -> Result<String, String> {
+        set_schedule_lines(tool, schedule_id, Vec::new())?;
+
+        let env_path = get_env_path(tool, schedule_id)?;
+        if env_path.exists() {
+            fs::remove_file(&env_path)
+                .map_err(|e| format!("Failed to delete schedule env file: {}", e))?;
+            Ok(format!("Env file deleted: {}", env_path.display()))
+        } else {
+            Err(format!("Env file not found for tool: {}", tool))
+        }
+    }
+
+    /// Every schedule tagged in the managed block, read from its env file
+    fn legacy_schedules(&self) -> Result<Vec<RegisteredSchedule>, String> {
+        let block = managed_lines(&read_crontab()?);
+        let mut ids: Vec<(String, String)> = Vec::new();
+        for ids_in_line in block.iter().filter_map(|line| parse_job_tag(line)) {
+            if !ids.contains(&ids_in_line) {
+                ids.push(ids_in_line);
+            }
+        }
+
+        let mut schedules = Vec::new();
+        for (tool, schedule_id) in ids {
+            if let Ok(Some(schedule)) = load_schedule(&tool, &schedule_id, &block) {
+                schedules.push(schedule);
+            }
+        }
+        Ok(schedules)
+    }
+
+    /// Write the schedule's trigger lines into the managed block
+    fn load_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        let schedule = self
+            .get_registered_schedule(tool, schedule_id)?
+            .ok_or_else(|| format!("Schedule not found: {}", schedule_id))?;
+        let config = LaunchdConfig::from_registered(&schedule)?;
+        set_schedule_lines(tool, schedule_id, job_lines(&config)?)
+    }
+
+    /// Swap the trigger lines for a paused placeholder, keeping the env file
+    fn unload_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        set_schedule_lines(
+            tool,
+            schedule_id,
+            vec![format!("{} {}", PAUSED_LINE, job_tag(tool, schedule_id))],
+        )
+    }
+
+    /// Run the job's command in the background, as cron would
+    fn start_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        // Start from a cron-like environment, so variables of the calling run (usually the
+        // upstream schedule) cannot leak into this one
+        let base_env = std::env::vars()
+            .filter(|(key, _)| ["HOME", "PATH", "USER", "LOGNAME", "SHELL", "LANG"].contains(&key.as_str()));
+        Command::new("/bin/sh")
+            .arg("-c")
+            .arg(job_command(tool, schedule_id)?)
+            .env_clear()
+            .envs(base_env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to start job: {}", e))?;
+        Ok(())
+    }
+
+    fn installed_calendar(&self, tool: &str, schedule_id: &str) -> Result<Vec<CalendarInterval>, String> {
+        let tag = job_tag(tool, schedule_id);
+        Ok(managed_lines(&read_crontab()?)
+            .iter()
+            .filter(|line| !line.starts_with('#') && line.ends_with(&tag))
+            .filter_map(|line| parse_cron_fields(line))
+            .flatten()
+            .collect())
+    }
+
+    fn reconcile_schedules(&self, repair: bool) -> Result<Vec<ScheduleDrift>, String> {
+        let block = managed_lines(&read_crontab()?);
+        let env_files = env_files_in(&get_env_dir()?)?;
+        let tagged: BTreeSet<_> = block.iter().filter_map(|line| parse_job_tag(line)).collect();
+        let lines_of = |ids: &(String, String)| -> Vec<String> {
+            block
+                .iter()
+                .filter(|line| parse_job_tag(line).as_ref() == Some(ids))
+                .cloned()
+                .collect()
+        };
+        let is_paused = |line: &String| line.starts_with(PAUSED_LINE);
+
+        let mut drifts = Vec::new();
+        let mut known = BTreeSet::new();
+        for schedule in self.get_registered_schedules()? {
+            let ids = (schedule.tool.clone(), schedule.schedule_id.clone());
+            let config = LaunchdConfig::from_registered(&schedule)?;
+
+            let mut kinds = Vec::new();
+            let mut push = |kind: &'static str| {
+                if !kinds.contains(&kind) {
+                    kinds.push(kind);
+                }
+            };
+            match fs::read_to_string(get_env_path(&ids.0, &ids.1)?) {
+                Err(_) => push("missing-config"),
+                Ok(content) => job_drift(
+                    &content,
+                    &env_file(&config)?,
+                    |content: &String| portable_env_file(content),
+                    "config-differs",
+                )
+                .into_iter()
+                .for_each(&mut push),
+            }
+            let installed = lines_of(&ids);
+            if installed.is_empty() {
+                push("missing-agent");
+            } else if config.enabled && installed.iter().all(is_paused) {
+                push("not-loaded");
+            } else if !config.enabled && !installed.iter().all(is_paused) {
+                push("paused-but-loaded");
+            } else {
+                job_drift(
+                    &installed,
+                    &schedule_lines(&config)?,
+                    |lines: &Vec<String>| portable_lines(lines),
+                    "agent-differs",
+                )
+                .into_iter()
+                .for_each(&mut push);
+            }
+
+            let repaired = (repair && !kinds.is_empty()).then(|| self.write_job(&config).map(|_| ()));
+            drifts.extend(drift_entries(&ids, &kinds, repaired));
+            known.insert(ids);
+        }
+
+        let orphans: BTreeSet<_> = env_files
+            .iter()
+            .chain(&tagged)
+            .filter(|ids| !known.contains(*ids))
+            .collect();
+        for ids in orphans {
+            let mut kinds = Vec::new();
+            if env_files.contains(ids) {
+                kinds.push("orphan-file");
+            }
+            if tagged.contains(ids) {
+                kinds.push("orphan-job");
+            }
+
+            let repaired = repair.then(|| {
+                if tagged.contains(ids) {
+                    set_schedule_lines(&ids.0, &ids.1, Vec::new())?;
+                }
+                let env_path = get_env_path(&ids.0, &ids.1)?;
+                if env_path.exists() {
+                    fs::remove_file(&env_path)
+                        .map_err(|e| format!("Failed to delete {}: {}", env_path.display(), e))?;
+                }
+                Ok(())
+            });
+            drifts.extend(drift_entries(ids, &kinds, repaired));
+        }
+        Ok(drifts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_CRONTAB: &str = "MAILTO=me@example.com\n# nightly backup\n0 3 * * * /usr/local/bin/backup\n";
+
+    fn job_line(schedule: &str, time: &str) -> String {
+        format!("{} * * * /bin/sh run {}", time, job_tag("claude", schedule))
+    }
+
+    #[test]
+    fn keeps_the_users_lines_outside_the_block() {
+        let crontab = with_schedule_lines(USER_CRONTAB, "claude", "a", vec![job_line("a", "0 9")]);
+        assert!(crontab.starts_with(USER_CRONTAB));
+        assert_eq!(managed_lines(&crontab), vec![job_line("a", "0 9")]);
+
+        // Lines after the block stay too; the block moves to the end
+        let edited = crontab.replace(BLOCK_END, &format!("{}\n*/5 * * * * uptime", BLOCK_END));
+        let crontab = with_schedule_lines(&edited, "claude", "b", vec![job_line("b", "30 8")]);
+        assert!(crontab.starts_with(&format!("{}*/5 * * * * uptime\n{}", USER_CRONTAB, BLOCK_BEGIN)));
+        assert_eq!(managed_lines(&crontab), vec![job_line("a", "0 9"), job_line("b", "30 8")]);
+    }
+
+    #[test]
+    fn rerendering_a_schedule_is_idempotent() {
+        let once = with_schedule_lines(USER_CRONTAB, "claude", "a", vec![job_line("a", "0 9")]);
+        let twice = with_schedule_lines(&once, "claude", "a", vec![job_line("a", "0 9")]);
+        assert_eq!(once, twice);
+
+        let moved = with_schedule_lines(&twice, "claude", "a", vec![job_line("a", "15 10")]);
+        assert_eq!(managed_lines(&moved), vec![job_line("a", "15 10")]);
+        assert!(moved.ends_with(&format!("{}\n", BLOCK_END)));
+    }
+
+    #[test]
+    fn removing_the_last_schedule_drops_the_block() {
+        let crontab = with_schedule_lines(USER_CRONTAB, "claude", "a", vec![job_line("a", "0 9")]);
+        assert_eq!(with_schedule_lines(&crontab, "claude", "a", Vec::new()), USER_CRONTAB);
+        assert_eq!(with_schedule_lines("", "claude", "a", Vec::new()), "\n");
+    }
+
+    #[test]
+    fn job_tags_round_trip() {
+        let line = job_line("20250101090000000", "0 9");
+        assert_eq!(
+            parse_job_tag(&line),
+            Some(("claude".to_string(), "20250101090000000".to_string()))
+        );
+        assert_eq!(parse_job_tag("0 3 * * * /usr/local/bin/backup"), None);
+    }
+
+    #[test]
+    fn install_paths_are_left_out_of_the_comparison() {
+        let line = |script: &str| {
+            format!(
+                "0 9 * * * . '/home/me/env' && /bin/bash '{}' >> '/home/me/claude.log' 2>> '/home/me/claude.error.log' {}",
+                script,
+                job_tag("claude", "a")
+            )
+        };
+        let installed = vec![line("/Applications/Old.app/run-tool.sh")];
+        let expected = vec![line("/Applications/New.app/run-tool.sh")];
+        assert_eq!(
+            job_drift(&installed, &expected, |lines: &Vec<String>| portable_lines(lines), "agent-differs"),
+            Some("install-differs")
+        );
+        let moved = vec![line("/Applications/New.app/run-tool.sh").replacen("0 9", "0 10", 1)];
+        assert_eq!(
+            job_drift(&installed, &moved, |lines: &Vec<String>| portable_lines(lines), "agent-differs"),
+            Some("agent-differs")
+        );
+
+        let env = |bin: &str| format!("# header\nexport SCHEDULER_BIN='{}'\nexport TOOL='claude'\n", bin);
+        assert_eq!(portable_env_file(&env("/old/bin")), portable_env_file(&env("/new/bin")));
+        assert_ne!(portable_env_file(&env("/old/bin")), portable_env_file(&env("/old/bin").replace("claude", "codex")));
+    }
+
+    #[test]
+    fn failed_load_rolls_back_to_the_previous_job() {
+        use crate::scheduler::tests::{config, FakeScheduler};
+
+        let fake = FakeScheduler::new(
+            "crontab",
+            "crontab",
+            &[(
+                "crontab",
+                r#"case "$1" in
+    -l) cat '$ROOT/crontab' 2>/dev/null || { echo 'no crontab for user' >&2; exit 1; } ;;
+    -) fail_once && exit 1; cat > '$ROOT/crontab' ;;
+esac"#,
+            )],
+        );
+        let backend = CrontabBackend;
+        let first = config("1", "first");
+        backend.create_schedule(&first).unwrap();
+
+        let mut moved = config("1", "moved");
+        moved.times = vec![(10, 30)];
+        fake.fail_next();
+        assert!(backend.create_schedule(&moved).is_err());
+        assert_eq!(
+            backend.installed_calendar("claude", "1").unwrap(),
+            plist_manager::calendar_intervals(&first).unwrap()
+        );
+        assert_eq!(backend.get_registered_schedule("claude", "1").unwrap().unwrap().title, "first");
+    }
+}
@@ -0,0 +1,210 @@
+use crate::plist_manager::{self, CalendarInterval, LaunchdConfig, RegisteredSchedule};
+use crate::scheduler::SchedulerBackend;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use std::fs;
+use std::sync::Mutex;
+
+/// Schedules fired by the app's own timer loop (`run_in_process_scheduler`) straight from
+/// the schedule store, without registering anything with the OS. Runs only happen while
+/// the app is running; missed ones are caught up at the next start.
+pub struct InProcessBackend;
+
+/// Where this backend kept its schedules before the schedule store existed
+const LEGACY_SCHEDULES_FILE: &str = "in-process-schedules.json";
+
+/// Schedules `start_job` asked to run, picked up by the next tick of the timer loop
+static PENDING_STARTS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+/// Take the (tool, schedule ID) pairs queued by `start_job`
+pub fn take_pending_starts() -> Vec<(String, String)> {
+    std::mem::take(&mut *PENDING_STARTS.lock().unwrap())
+}
+
+/// Start delay of a jittered schedule on a given day: 0..=N minutes in seconds, stable
+/// within the day. The same delay as run-tool.sh, which seeds it with `cksum` of
+/// "<schedule ID>-<day>", so a schedule starts at the same time on every backend.
+fn jitter_delay(schedule: &RegisteredSchedule, day: &str) -> Duration {
+    let Some(minutes) = schedule.jitter_minutes.filter(|minutes| *minutes > 0) else {
+        return Duration::zero();
+    };
+    let seed = cksum(format!("{}-{}", schedule.schedule_id, day).as_bytes());
+    Duration::seconds((seed as u64 % (minutes as u64 * 60 + 1)) as i64)
+}
+
+/// POSIX `cksum`: CRC-32 (polynomial 0x04C11DB7, most significant bit first) over the
+/// data followed by its length, complemented
+fn cksum(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0;
+    let mut feed = |byte: u8| {
+        crc ^= (byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04C1_1DB7
+            } else {
+                crc << 1
+            };
+        }
+    };
+    data.iter().for_each(|byte| feed(*byte));
+    let mut length = data.len();
+    while length > 0 {
+        feed((length & 0xff) as u8);
+        length >>= 8;
+    }
+    !crc
+}
+
+/// Slots of a schedule whose start time, after the jitter delay, falls in `(from, to]`
+pub fn due_slots(
+    schedule: &RegisteredSchedule,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<DateTime<FixedOffset>> {
+    let max_delay = Duration::minutes(schedule.jitter_minutes.unwrap_or(0) as i64);
+    crate::occurrence::scheduled_between_in_zone(schedule, from - max_delay, to)
+        .into_iter()
+        .filter(|slot| {
+            let day = slot.format("%Y-%m-%d").to_string();
+            let start = slot.with_timezone(&Utc) + jitter_delay(schedule, &day);
+            from < start && start <= to
+        })
+        .collect()
+}
+
+impl SchedulerBackend for InProcessBackend {
+    fn name(&self) -> &'static str {
+        "in-process"
+    }
+
+    /// Nothing to write besides the store; only check the triggers like the OS backends do
+    fn write_job(&self, config: &LaunchdConfig) -> Result<String, String> {
+        let sub_daily_minutes = if config.schedule_type == "interval" {
+            plist_manager::sub_daily_interval_minutes(&config.interval_unit, config.interval_value)
+        } else {
+            None
+        };
+        match sub_daily_minutes {
+            Some(0) => return Err("Interval must be at least one minute".to_string()),
+            Some(_) => {}
+            None if config.schedule_type == "chain" => {}
+            None => {
+                plist_manager::calendar_intervals(config)?;
+            }
+        }
+
+        if config.enabled {
+            Ok(format!("Schedule {} saved and run by the app", config.schedule_id))
+        } else {
+            Ok(format!("Schedule {} saved (paused)", config.schedule_id))
+        }
+    }
+
+    fn remove_job(&self, _tool: &str, schedule_id: &str) -> Result<String, String> {
+        Ok(format!("Schedule deleted: {}", schedule_id))
+    }
+
+    fn legacy_schedules(&self) -> Result<Vec<RegisteredSchedule>, String> {
+        let path = plist_manager::get_config_dir()?.join(LEGACY_SCHEDULES_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read in-process schedules: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse in-process schedules: {}", e))
+    }
+
+    /// The timer loop fires every enabled schedule in the store, so there is nothing to load
+    fn load_job(&self, _tool: &str, _schedule_id: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn unload_job(&self, _tool: &str, _schedule_id: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn start_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        PENDING_STARTS
+            .lock()
+            .unwrap()
+            .push((tool.to_string(), schedule_id.to_string()));
+        Ok(())
+    }
+
+    fn installed_calendar(&self, _tool: &str, _schedule_id: &str) -> Result<Vec<CalendarInterval>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Slots are evaluated in each schedule's own zone on every tick, so there is no
+    /// translated calendar to keep up to date
+    fn refresh_zoned_schedule(&self, _schedule: &RegisteredSchedule) -> Result<bool, String> {
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(value: serde_json::Value) -> RegisteredSchedule {
+        let mut base = serde_json::json!({
+            "tool": "claude",
+            "schedule_id": "20250101000000000",
+            "title": "test",
+            "execution_time": ["09:00", "18:30"],
+            "target_directory": "/tmp",
+            "command_args": "run",
+            "created_at": "",
+            "schedule_type": "daily",
+            "interval_value": null,
+            "start_date": null,
+            "timezone": "Asia/Tokyo",
+        });
+        base.as_object_mut()
+            .unwrap()
+            .extend(value.as_object().unwrap().clone());
+        serde_json::from_value(base).unwrap()
+    }
+
+    fn utc(hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 10, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn slot_is_due_in_exactly_one_tick() {
+        let schedule = schedule(serde_json::json!({}));
+        // 09:00 in Tokyo is 00:00 UTC
+        assert_eq!(due_slots(&schedule, utc(23, 59, 30) - Duration::days(1), utc(0, 0, 0)).len(), 1);
+        assert!(due_slots(&schedule, utc(0, 0, 0), utc(0, 0, 30)).is_empty());
+        let ticks = (0..24 * 120)
+            .map(|tick| utc(0, 0, 0) + Duration::seconds(tick * 30))
+            .collect::<Vec<_>>();
+        let fired: usize = ticks
+            .windows(2)
+            .map(|tick| due_slots(&schedule, tick[0], tick[1]).len())
+            .sum();
+        assert_eq!(fired, 1); // 18:30 Tokyo; 09:00 fell in the first boundary tick
+    }
+
+    #[test]
+    fn cksum_matches_the_run_scripts() {
+        // printf '%s' ... | cksum
+        assert_eq!(cksum(b""), 4294967295);
+        assert_eq!(cksum(b"abc"), 1219131554);
+        assert_eq!(cksum(b"20250101000000000-2025-03-10"), 196918929);
+        let schedule = schedule(serde_json::json!({ "jitter_minutes": 30 }));
+        assert_eq!(jitter_delay(&schedule, "2025-03-10"), Duration::seconds(196918929 % 1801));
+    }
+
+    #[test]
+    fn jittered_slot_starts_late_but_once() {
+        let schedule = schedule(serde_json::json!({ "jitter_minutes": 30 }));
+        let delay = jitter_delay(&schedule, "2025-03-10");
+        assert!(delay <= Duration::minutes(30));
+        assert_eq!(delay, jitter_delay(&schedule, "2025-03-10"));
+
+        let start = utc(0, 0, 0) + delay;
+        assert_eq!(due_slots(&schedule, start - Duration::seconds(1), start).len(), 1);
+        assert!(due_slots(&schedule, start, start + Duration::minutes(60)).is_empty());
+    }
+}
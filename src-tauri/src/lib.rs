@@ -1,6 +1,4 @@
-use chrono::{
-    DateTime, Datelike, Local, LocalResult, NaiveDate, NaiveTime, TimeZone, Timelike, Utc,
-};
+use chrono::{DateTime, NaiveDate, TimeZone, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::Command;
@@ -11,7 +9,17 @@ use std::{
 };
 use tauri::{Manager, State};
 
+mod blackout;
+mod conflict;
+mod cron;
+mod crontab_manager;
+mod in_process_manager;
+mod occurrence;
 mod plist_manager;
+mod schedule_store;
+mod scheduler;
+mod systemd_manager;
+use occurrence::parse_schedule_time;
 use plist_manager::{LaunchdConfig, RegisteredSchedule};
 
 #[derive(Clone)]
@@ -60,6 +68,8 @@ struct ScheduleResult {
     registered_tool: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     schedule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warnings: Option<Vec<conflict::ScheduleConflict>>, // nearby runs of related schedules
 }
 
 #[derive(Serialize, Deserialize)]
@@ -70,123 +80,174 @@ struct ScheduleHistoryEntry {
     status: String,
 }
 
-fn parse_schedule_time(execution_time: &str) -> Option<NaiveTime> {
-    let parts: Vec<&str> = execution_time.split(':').collect();
-    if parts.len() != 2 {
-        return None;
-    }
-    let hour: u32 = parts[0].parse().ok()?;
-    let minute: u32 = parts[1].parse().ok()?;
-    NaiveTime::from_hms_opt(hour, minute, 0)
+/// Runs of related schedules closer than this (in minutes) are reported as conflicts
+const DEFAULT_CONFLICT_WINDOW_MINUTES: u32 = 5;
+
+/// Upper bound for the random start delay, so a jittered run stays near its slot
+const MAX_JITTER_MINUTES: u32 = 120;
+
+/// Upper bound for get_next_occurrences, which walks the calendar day by day
+const MAX_NEXT_OCCURRENCES: u32 = 100;
+
+/// Widest range get_schedule_calendar accepts; minute intervals expand to many slots
+const MAX_CALENDAR_RANGE_DAYS: i64 = 92;
+
+/// How often the in-process backend's timer loop looks for due schedules, in seconds
+const IN_PROCESS_TICK_SECONDS: u64 = 30;
+
+/// How often a run checks whether its tool exited, in seconds
+const RUN_POLL_SECONDS: u64 = 2;
+
+/// One expected run in the calendar view, merged with what the history says happened
+#[derive(Serialize)]
+struct CalendarOccurrence {
+    schedule_id: String,
+    tool: String,
+    title: String,
+    scheduled_at: String, // RFC 3339 in the schedule's zone
+    status: String,       // "scheduled", "ran", "failed", "skipped", "missed"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    history_status: Option<String>, // history entry the status was derived from
 }
 
-fn local_datetime_from_date_time(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
-    let naive = date.and_time(time);
-    match Local.from_local_datetime(&naive) {
-        LocalResult::Single(dt) => Some(dt),
-        LocalResult::Ambiguous(earliest, _) => Some(earliest),
-        LocalResult::None => None,
+/// Validate "HH:MM" execution times into sorted, de-duplicated (hour, minute) pairs
+fn parse_execution_times(execution_times: &[String]) -> Result<Vec<(u32, u32)>, String> {
+    if execution_times.is_empty() {
+        return Err("実行時刻を1つ以上指定してください".to_string());
     }
-}
 
-fn get_last_scheduled_time(
-    schedule: &RegisteredSchedule,
-    now: DateTime<Local>,
-) -> Option<DateTime<Local>> {
-    let time = parse_schedule_time(&schedule.execution_time)?;
-    let today = now.date_naive();
-
-    match schedule.schedule_type.as_str() {
-        "daily" => {
-            let mut candidate = local_datetime_from_date_time(today, time)?;
-            if candidate > now {
-                let yesterday = today.pred_opt()?;
-                candidate = local_datetime_from_date_time(yesterday, time)?;
-            }
-            Some(candidate)
-        }
-        "weekly" => {
-            let start_date_str = schedule.start_date.as_ref()?;
-            let start_date = NaiveDate::parse_from_str(start_date_str, "%Y-%m-%d").ok()?;
-            let target_weekday = start_date.weekday();
-            let today_weekday = today.weekday();
-            let days_back = (7
-                + today_weekday.num_days_from_monday() as i64
-                - target_weekday.num_days_from_monday() as i64)
-                % 7;
-            let mut date = today - chrono::Duration::days(days_back);
-            let mut candidate = local_datetime_from_date_time(date, time)?;
-            if candidate > now {
-                date = date - chrono::Duration::days(7);
-                candidate = local_datetime_from_date_time(date, time)?;
-            }
-            Some(candidate)
-        }
-        "interval" => {
-            let start_date_str = schedule.start_date.as_ref()?;
-            let interval = schedule.interval_value? as i64;
-            let start_date = NaiveDate::parse_from_str(start_date_str, "%Y-%m-%d").ok()?;
-            if today < start_date {
-                return None;
-            }
-            let days_since = (today - start_date).num_days();
-            let mut last_offset = days_since - (days_since % interval);
-            let mut last_date = start_date + chrono::Duration::days(last_offset);
-            let mut candidate = local_datetime_from_date_time(last_date, time)?;
-            if candidate > now {
-                if last_offset < interval {
-                    return None;
-                }
-                last_offset -= interval;
-                last_date = start_date + chrono::Duration::days(last_offset);
-                candidate = local_datetime_from_date_time(last_date, time)?;
-            }
-            Some(candidate)
+    let mut times = Vec::with_capacity(execution_times.len());
+    for execution_time in execution_times {
+        let parts: Vec<&str> = execution_time.split(':').collect();
+        if parts.len() != 2 {
+            return Err("時刻の形式が正しくありません（HH:MM形式で指定してください）".to_string());
         }
-        _ => None,
+        let hour: u32 = match parts[0].parse() {
+            Ok(h) if h <= 23 => h,
+            _ => return Err("時間は0-23の範囲で指定してください".to_string()),
+        };
+        let minute: u32 = match parts[1].parse() {
+            Ok(m) if m <= 59 => m,
+            _ => return Err("分は0-59の範囲で指定してください".to_string()),
+        };
+        times.push((hour, minute));
+    }
+    times.sort_unstable();
+    times.dedup();
+    Ok(times)
+}
+
+/// Map weekday numbers onto 0 = Sun .. 6 = Sat (7 is also Sunday), dropping invalid values
+fn normalize_weekdays(weekdays: Option<Vec<u32>>) -> Option<Vec<u32>> {
+    let mut weekdays: Vec<u32> = weekdays?
+        .into_iter()
+        .filter(|weekday| *weekday <= 7)
+        .map(|weekday| weekday % 7)
+        .collect();
+    weekdays.sort_unstable();
+    weekdays.dedup();
+    if weekdays.is_empty() {
+        None
+    } else {
+        Some(weekdays)
     }
 }
 
-fn load_last_history_map() -> Result<HashMap<String, DateTime<Utc>>, String> {
-    let config_dir = dirs::config_dir()
-        .ok_or("Could not determine config directory".to_string())?
-        .join("tauri-cli-scheduler");
+fn format_execution_times(times: &[(u32, u32)]) -> String {
+    times
+        .iter()
+        .map(|(hour, minute)| format!("{}:{:02}", hour, minute))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn read_history_entries() -> Result<Vec<ScheduleHistoryEntry>, String> {
+    let config_dir = plist_manager::get_config_dir()?;
     let history_path = config_dir.join("schedule-history.jsonl");
 
     if !history_path.exists() {
-        return Ok(HashMap::new());
+        return Ok(Vec::new());
     }
 
     let file =
         File::open(&history_path).map_err(|e| format!("Failed to read history file: {}", e))?;
     let reader = BufReader::new(file);
 
-    let mut latest_map: HashMap<String, DateTime<Utc>> = HashMap::new();
+    let mut entries = Vec::new();
     for line in reader.lines() {
         let line = match line {
             Ok(val) => val,
             Err(_) => continue,
         };
-        let entry: ScheduleHistoryEntry = match serde_json::from_str(&line) {
-            Ok(val) => val,
+        match serde_json::from_str::<ScheduleHistoryEntry>(&line) {
+            Ok(entry) => entries.push(entry),
             Err(_) => continue,
-        };
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Key of a schedule's history entries. IDs are only unique per tool.
+type HistoryKey = (String, String); // tool, schedule ID
+
+fn history_key(schedule: &RegisteredSchedule) -> HistoryKey {
+    (schedule.tool.clone(), schedule.schedule_id.clone())
+}
+
+fn load_last_history_map() -> Result<HashMap<HistoryKey, DateTime<Utc>>, String> {
+    let mut latest_map: HashMap<HistoryKey, DateTime<Utc>> = HashMap::new();
+    for entry in read_history_entries()? {
         let timestamp = match DateTime::parse_from_rfc3339(&entry.timestamp) {
             Ok(val) => val.with_timezone(&Utc),
             Err(_) => continue,
         };
-        let update = match latest_map.get(&entry.schedule_id) {
+        let key = (entry.tool, entry.schedule_id);
+        let update = match latest_map.get(&key) {
             Some(existing) => timestamp > *existing,
             None => true,
         };
         if update {
-            latest_map.insert(entry.schedule_id.clone(), timestamp);
+            latest_map.insert(key, timestamp);
         }
     }
 
     Ok(latest_map)
 }
 
+/// Successful runs per schedule, counting both launchd runs and catch-up runs
+fn load_success_count_map() -> Result<HashMap<HistoryKey, u32>, String> {
+    let mut counts: HashMap<HistoryKey, u32> = HashMap::new();
+    for entry in read_history_entries()? {
+        if entry.status == "success" || entry.status == "catchup-success" {
+            *counts.entry((entry.tool, entry.schedule_id)).or_insert(0) += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// Whether a recurring schedule has used up its end date or run budget
+fn is_schedule_finished(schedule: &RegisteredSchedule, today: NaiveDate, success_count: u32) -> bool {
+    if let Some(max_runs) = schedule.max_runs {
+        if success_count >= max_runs {
+            return true;
+        }
+    }
+    schedule
+        .end_date
+        .as_ref()
+        .and_then(|date_str| NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok())
+        .map(|end_date| today > end_date)
+        .unwrap_or(false)
+}
+
+fn unregister_finished_schedule(schedule: &RegisteredSchedule) {
+    let _ = append_schedule_history(&schedule.schedule_id, &schedule.tool, "expired");
+    if let Err(err) = scheduler::backend().delete_schedule(&schedule.tool, &schedule.schedule_id) {
+        eprintln!("Failed to unregister finished schedule {}: {}", schedule.schedule_id, err);
+    }
+}
+
 fn append_schedule_history(schedule_id: &str, tool: &str, status: &str) -> Result<(), String> {
     let config_dir = plist_manager::ensure_config_dir()?;
     let history_path = config_dir.join("schedule-history.jsonl");
@@ -209,15 +270,46 @@ fn append_schedule_history(schedule_id: &str, tool: &str, status: &str) -> Resul
     Ok(())
 }
 
+/// Point the history entries of a schedule at a new ID
+fn rename_schedule_history(tool: &str, from: &str, to: &str) -> Result<(), String> {
+    let config_dir = plist_manager::get_config_dir()?;
+    let history_path = config_dir.join("schedule-history.jsonl");
+    if !history_path.exists() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(&history_path)
+        .map_err(|e| format!("Failed to read history file: {}", e))?;
+
+    let mut renamed = String::new();
+    for line in content.lines() {
+        match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(mut entry) if entry["tool"] == tool && entry["schedule_id"] == from => {
+                entry["schedule_id"] = serde_json::Value::from(to);
+                renamed.push_str(&entry.to_string());
+            }
+            _ => renamed.push_str(line),
+        }
+        renamed.push('\n');
+    }
+
+    let temp_path = history_path.with_extension("jsonl.tmp");
+    std::fs::write(&temp_path, renamed)
+        .map_err(|e| format!("Failed to write history file: {}", e))?;
+    std::fs::rename(&temp_path, &history_path)
+        .map_err(|e| format!("Failed to write history file: {}", e))
+}
+
 fn build_catchup_applescript(
     tool: &str,
     target_directory: &str,
     options: &str,
     command: &str,
+    status_file: &str,
 ) -> Result<String, String> {
     let escaped_target_directory = escape_applescript_string(target_directory);
     let escaped_options = escape_applescript_string(options);
     let escaped_command = escape_applescript_string(command);
+    let escaped_status_file = escape_applescript_string(status_file);
 
     let script = match tool {
         "claude" => format!(
@@ -225,54 +317,69 @@ fn build_catchup_applescript(
 property targetDirectory : "{}"
 property toolOptions : "{}"
 property toolCommand : "{}"
+property statusFile : "{}"
 
 tell application "iTerm"
     activate
     create window with default profile
 
+    -- Lets the app close the session when the run is stopped
+    set sessionId to id of current session of current window
     tell current session of current window
         write text "cd " & quoted form of targetDirectory
-        write text "claude " & toolOptions & " " & quoted form of toolCommand
+        -- The exit status tells the app when the tool finished and whether it succeeded
+        write text "claude " & toolOptions & " " & quoted form of toolCommand & "; echo $? > " & quoted form of statusFile
     end tell
+    return sessionId
 end tell
             "#,
-            escaped_target_directory, escaped_options, escaped_command
+            escaped_target_directory, escaped_options, escaped_command, escaped_status_file
         ),
         "codex" => format!(
             r#"
 property targetDirectory : "{}"
 property toolOptions : "{}"
 property toolCommand : "{}"
+property statusFile : "{}"
 
 tell application "iTerm"
     activate
     create window with default profile
 
+    -- Lets the app close the session when the run is stopped
+    set sessionId to id of current session of current window
     tell current session of current window
         write text "cd " & quoted form of targetDirectory
-        write text "codex " & toolOptions & " " & quoted form of toolCommand
+        -- The exit status tells the app when the tool finished and whether it succeeded
+        write text "codex " & toolOptions & " " & quoted form of toolCommand & "; echo $? > " & quoted form of statusFile
     end tell
+    return sessionId
 end tell
             "#,
-            escaped_target_directory, escaped_options, escaped_command
+            escaped_target_directory, escaped_options, escaped_command, escaped_status_file
         ),
         "gemini" => format!(
             r#"
 property targetDirectory : "{}"
 property toolOptions : "{}"
 property toolCommand : "{}"
+property statusFile : "{}"
 
 tell application "iTerm"
     activate
     create window with default profile
 
+    -- Lets the app close the session when the run is stopped
+    set sessionId to id of current session of current window
     tell current session of current window
         write text "cd " & quoted form of targetDirectory
-        write text "gemini " & toolOptions & " --prompt " & quoted form of toolCommand
+        -- The exit status tells the app when the tool finished and whether it succeeded
+        write text "gemini " & toolOptions & " --prompt " & quoted form of toolCommand & "; echo $? > " & quoted form of statusFile
     end tell
+    return sessionId
 end tell
             "#,
-            escaped_target_directory, escaped_options, escaped_command
+            escaped_target_directory, escaped_options, escaped_command, escaped_status_file
         ),
         _ => return Err("無効なツール指定です".to_string()),
     };
@@ -280,10 +387,27 @@ end tell
     Ok(script)
 }
 
-async fn execute_schedule_catchup(
+/// How a run of `execute_schedule` ended, when it did not fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunOutcome {
+    Succeeded,
+    /// The tool was still busy with another run, so this one never started
+    Skipped,
+    /// Stopped through `stop_execution` before the tool finished
+    Cancelled,
+}
+
+/// Run a schedule from within the app, tracked like a manual run: in a new iTerm window
+/// where iTerm is available, headless otherwise. The run ends when the tool exits, so its
+/// status and the chained schedules it starts follow the tool's own exit code. History
+/// statuses get `status_prefix` ("catchup-" for catch-up runs, "" for runs of the
+/// in-process backend, which stand in for the run scripts).
+async fn execute_schedule(
     schedule: &RegisteredSchedule,
     state: &AppState,
-) -> Result<(), String> {
+    status_prefix: &str,
+) -> Result<RunOutcome, String> {
+    let status = |name: &str| format!("{}{}", status_prefix, name);
     let tool_state = match schedule.tool.as_str() {
         "claude" => &state.claude,
         "codex" => &state.codex,
@@ -294,8 +418,8 @@ async fn execute_schedule_catchup(
     {
         let mut is_running = tool_state.is_running.lock().unwrap();
         if *is_running {
-            append_schedule_history(&schedule.schedule_id, &schedule.tool, "catchup-skipped-running")?;
-            return Ok(());
+            append_schedule_history(&schedule.schedule_id, &schedule.tool, &status("skipped-running"))?;
+            return Ok(RunOutcome::Skipped);
         }
         *is_running = true;
 
@@ -306,73 +430,362 @@ async fn execute_schedule_catchup(
     if !std::path::Path::new(&schedule.target_directory).exists() {
         let mut is_running = tool_state.is_running.lock().unwrap();
         *is_running = false;
-        append_schedule_history(&schedule.schedule_id, &schedule.tool, "catchup-failure")?;
+        append_schedule_history(&schedule.schedule_id, &schedule.tool, &status("failure"))?;
+        let _ = start_followups(&schedule.tool, &schedule.schedule_id, false);
         return Err(format!(
             "ディレクトリが存在しません: {}",
             schedule.target_directory
         ));
     }
 
+    append_schedule_history(&schedule.schedule_id, &schedule.tool, &status("started"))?;
+    let result = if iterm_available() {
+        run_in_iterm(schedule, tool_state).await
+    } else {
+        run_headless(schedule, tool_state).await
+    };
+
+    {
+        let mut is_running = tool_state.is_running.lock().unwrap();
+        *is_running = false;
+    }
+
+    let outcome = match &result {
+        Ok(RunOutcome::Cancelled) => "cancelled",
+        Ok(_) => "success",
+        Err(_) => "failure",
+    };
+    append_schedule_history(&schedule.schedule_id, &schedule.tool, &status(outcome))?;
+    // A stopped run neither succeeded nor failed, so it starts no chained schedule
+    if result != Ok(RunOutcome::Cancelled) {
+        let _ = start_followups(&schedule.tool, &schedule.schedule_id, result.is_ok());
+    }
+    result
+}
+
+/// Whether runs can open iTerm: only on macOS, with iTerm installed
+fn iterm_available() -> bool {
+    cfg!(target_os = "macos")
+        && Command::new("osascript")
+            .arg("-e")
+            .arg(r#"tell application "Finder" to return exists application file id "com.googlecode.iterm2""#)
+            .output()
+            .is_ok_and(|output| {
+                output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true"
+            })
+}
+
+/// Run a schedule's tool without a terminal and wait for it to exit, like the run
+/// scripts outside macOS: through a login shell in the target directory, so the user's
+/// PATH applies, with its output going to the logs directory
+async fn run_headless(schedule: &RegisteredSchedule, tool_state: &ToolState) -> Result<RunOutcome, String> {
+    let shell_command = match schedule.tool.as_str() {
+        "claude" => r#"claude $TOOL_OPTIONS -p "$TOOL_COMMAND""#,
+        "codex" => r#"codex exec $TOOL_OPTIONS "$TOOL_COMMAND""#,
+        "gemini" => r#"gemini $TOOL_OPTIONS --prompt "$TOOL_COMMAND""#,
+        _ => return Err("無効なツール指定です".to_string()),
+    };
+    let logs_dir = plist_manager::ensure_config_dir()?.join("logs");
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let log_file = File::create(logs_dir.join(format!("{}-{}.log", schedule.tool, stamp)))
+        .map_err(|e| format!("Failed to create log file: {}", e))?;
+    let error_file = File::create(logs_dir.join(format!("{}-{}.error.log", schedule.tool, stamp)))
+        .map_err(|e| format!("Failed to create log file: {}", e))?;
+
+    let mut child = Command::new("bash")
+        .arg("-lc")
+        .arg(shell_command)
+        .current_dir(&schedule.target_directory)
+        .env("TOOL_OPTIONS", plist_manager::default_tool_options(&schedule.tool).unwrap_or_default())
+        .env("TOOL_COMMAND", &schedule.command_args)
+        .stdout(log_file)
+        .stderr(error_file)
+        .spawn()
+        .map_err(|e| format!("実行エラー: {}", e))?;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(exit)) if exit.success() => return Ok(RunOutcome::Succeeded),
+            Ok(Some(exit)) => return Err(format!("{} で終了しました", exit)),
+            Ok(None) => {}
+            Err(e) => return Err(format!("実行エラー: {}", e)),
+        }
+        if *tool_state.cancel_flag.lock().unwrap() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(RunOutcome::Cancelled);
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(RUN_POLL_SECONDS)).await;
+    }
+}
+
+/// Start a schedule's tool in a new iTerm window and wait until it exits there
+async fn run_in_iterm(schedule: &RegisteredSchedule, tool_state: &ToolState) -> Result<RunOutcome, String> {
+    // The tool writes its exit status here when it finishes
+    let status_path = plist_manager::ensure_config_dir()?.join("logs").join(format!(
+        "{}-{}-{}.status",
+        schedule.tool,
+        schedule.schedule_id,
+        Utc::now().timestamp_millis()
+    ));
     let options = plist_manager::default_tool_options(&schedule.tool).unwrap_or_default();
     let applescript = build_catchup_applescript(
         &schedule.tool,
         &schedule.target_directory,
         &options,
         &schedule.command_args,
+        &status_path.to_string_lossy(),
     )?;
 
-    append_schedule_history(&schedule.schedule_id, &schedule.tool, "catchup-started")?;
-    let output = Command::new("osascript").arg("-e").arg(&applescript).output();
-
-    let mut is_running = tool_state.is_running.lock().unwrap();
-    *is_running = false;
-
-    match output {
-        Ok(output) => {
-            if output.status.success() {
-                append_schedule_history(&schedule.schedule_id, &schedule.tool, "catchup-success")?;
-                Ok(())
-            } else {
-                append_schedule_history(&schedule.schedule_id, &schedule.tool, "catchup-failure")?;
-                Err(format!(
-                    "AppleScriptエラー: {}",
-                    String::from_utf8_lossy(&output.stderr)
-                ))
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(&applescript)
+        .output()
+        .map_err(|e| format!("実行エラー: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "AppleScriptエラー: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let session_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    // osascript returns as soon as the window is open; the tool is still running
+    loop {
+        if let Ok(content) = std::fs::read_to_string(&status_path) {
+            if let Ok(code) = content.trim().parse::<i32>() {
+                let _ = std::fs::remove_file(&status_path);
+                return if code == 0 {
+                    Ok(RunOutcome::Succeeded)
+                } else {
+                    Err(format!("終了コード {} で終了しました", code))
+                };
+            }
+        }
+        if std::mem::take(&mut *tool_state.cancel_flag.lock().unwrap()) {
+            // Closing the session ends the tool; if that fails, the run goes on and is
+            // tracked until the tool exits
+            match close_iterm_session(&session_id) {
+                Ok(()) => {
+                    let _ = std::fs::remove_file(&status_path);
+                    return Ok(RunOutcome::Cancelled);
+                }
+                Err(err) => eprintln!("Failed to stop the run of schedule {}: {}", schedule.schedule_id, err),
             }
         }
-        Err(e) => {
-            append_schedule_history(&schedule.schedule_id, &schedule.tool, "catchup-failure")?;
-            Err(format!("実行エラー: {}", e))
+        let iterm_running = Command::new("pgrep")
+            .arg("-x")
+            .arg("iTerm2")
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !iterm_running {
+            return Err("ツールの終了前にiTermが終了しました".to_string());
         }
+        tokio::time::sleep(std::time::Duration::from_secs(RUN_POLL_SECONDS)).await;
+    }
+}
+
+/// Close the iTerm session with the given ID, which ends the tool running in it
+fn close_iterm_session(session_id: &str) -> Result<(), String> {
+    if session_id.is_empty() {
+        return Err("iTermのセッションが特定できません".to_string());
+    }
+    let script = format!(
+        r#"
+tell application "iTerm"
+    repeat with aWindow in windows
+        repeat with aTab in tabs of aWindow
+            repeat with aSession in sessions of aTab
+                if id of aSession is "{}" then
+                    close aSession
+                    return
+                end if
+            end repeat
+        end repeat
+    end repeat
+end tell
+        "#,
+        escape_applescript_string(session_id)
+    );
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(&script)
+        .output()
+        .map_err(|e| format!("実行エラー: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "AppleScriptエラー: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
     }
+    Ok(())
 }
 
 async fn run_missed_schedules(state: AppState) -> Result<(), String> {
-    let now = Local::now();
-    let schedules = plist_manager::get_registered_schedules()?;
+    let now = Utc::now();
+    let schedules = scheduler::backend().get_registered_schedules()?;
     if schedules.is_empty() {
         return Ok(());
     }
 
     let last_history_map = load_last_history_map()?;
+    let success_count_map = load_success_count_map()?;
+    let blackout_calendars = blackout::load_calendars().unwrap_or_default();
 
     for schedule in schedules {
-        let last_scheduled_time = match get_last_scheduled_time(&schedule, now) {
-            Some(val) => val,
-            None => continue,
-        };
-        let last_scheduled_utc = last_scheduled_time.with_timezone(&Utc);
-        let last_run = last_history_map.get(&schedule.schedule_id);
-        let missed = match last_run {
-            Some(val) => *val < last_scheduled_utc,
-            None => true,
-        };
-        if missed {
-            let _ = append_schedule_history(&schedule.schedule_id, &schedule.tool, "wake-missed");
-            let _ = execute_schedule_catchup(&schedule, &state).await;
+        // Paused schedules are neither caught up nor expired until resumed
+        if !schedule.enabled {
+            continue;
+        }
+        let success_count = success_count_map
+            .get(&history_key(&schedule))
+            .copied()
+            .unwrap_or(0);
+        if schedule.max_runs.is_some_and(|max_runs| success_count >= max_runs) {
+            unregister_finished_schedule(&schedule);
+            continue;
+        }
+
+        let end_date = schedule
+            .end_date
+            .as_ref()
+            .and_then(|date_str| NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok());
+        // Occurrences on blackout days were skipped on purpose, not missed
+        let calendar_ids = schedule.blackout_calendars.clone().unwrap_or_default();
+        let last_scheduled_time = occurrence::last_scheduled_in_zone(&schedule, now)
+            .filter(|time| end_date.is_none_or(|end| time.date_naive() <= end))
+            .filter(|time| !blackout::is_blacked_out(&blackout_calendars, &calendar_ids, time.date_naive()));
+        if let Some(last_scheduled_time) = last_scheduled_time {
+            let last_scheduled_utc = last_scheduled_time.with_timezone(&Utc);
+            // launchd's StartInterval timer is not aligned with our slots, so a sub-daily
+            // schedule only counts as missed once a full interval passed without a run
+            let grace = if schedule.schedule_type == "interval" {
+                plist_manager::sub_daily_interval_minutes(&schedule.interval_unit, schedule.interval_value)
+                    .map(|minutes| chrono::Duration::minutes(minutes as i64))
+                    .unwrap_or_else(chrono::Duration::zero)
+            } else {
+                chrono::Duration::zero()
+            };
+            let last_run = last_history_map.get(&history_key(&schedule));
+            let missed = match last_run {
+                Some(val) => *val < last_scheduled_utc - grace,
+                None => true,
+            };
+            if missed {
+                let _ = append_schedule_history(&schedule.schedule_id, &schedule.tool, "wake-missed");
+                // Runs last until the tool exits, so each catch-up gets its own task
+                let state = state.clone();
+                tauri::async_runtime::spawn(async move {
+                    // A catch-up skipped because the tool was busy is not a run
+                    let ran = execute_schedule(&schedule, &state, "catchup-").await == Ok(RunOutcome::Succeeded);
+                    // The last allowed occurrence may just have been caught up, so check
+                    // limits once it is done
+                    let today = occurrence::today_in_zone(&schedule, now);
+                    if is_schedule_finished(&schedule, today, success_count + ran as u32) {
+                        unregister_finished_schedule(&schedule);
+                    }
+                });
+                continue;
+            }
+        }
+
+        if is_schedule_finished(&schedule, occurrence::today_in_zone(&schedule, now), success_count) {
+            unregister_finished_schedule(&schedule);
+        }
+    }
+
+    Ok(())
+}
+
+/// Timer loop of the in-process backend. Fires the schedules due since the previous tick
+/// and the ones queued by `start_job`, doing what the run scripts do for OS jobs.
+async fn run_in_process_scheduler(state: AppState) {
+    let mut last_tick = Utc::now();
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(IN_PROCESS_TICK_SECONDS)).await;
+        let now = Utc::now();
+        if let Err(err) = run_in_process_tick(&state, last_tick, now).await {
+            eprintln!("Failed to run in-process schedules: {}", err);
+        }
+        last_tick = now;
+    }
+}
+
+async fn run_in_process_tick(state: &AppState, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<(), String> {
+    let schedules = scheduler::backend().get_registered_schedules()?;
+    let mut due: Vec<RegisteredSchedule> = Vec::new();
+    for (tool, schedule_id) in in_process_manager::take_pending_starts() {
+        if let Some(schedule) = schedules
+            .iter()
+            .find(|schedule| schedule.tool == tool && schedule.schedule_id == schedule_id)
+        {
+            due.push(schedule.clone());
+        }
+    }
+    for schedule in &schedules {
+        if schedule.enabled
+            && !due
+                .iter()
+                .any(|other| other.tool == schedule.tool && other.schedule_id == schedule.schedule_id)
+            && !in_process_manager::due_slots(schedule, from, to).is_empty()
+        {
+            due.push(schedule.clone());
         }
     }
+    if due.is_empty() {
+        return Ok(());
+    }
+
+    let success_count_map = load_success_count_map()?;
+    let blackout_calendars = blackout::load_calendars().unwrap_or_default();
+    // A failure for one schedule must not keep the others from running
+    let record = |schedule: &RegisteredSchedule, status: &str| {
+        if let Err(err) = append_schedule_history(&schedule.schedule_id, &schedule.tool, status) {
+            eprintln!("Failed to record {} for schedule {}: {}", status, schedule.schedule_id, err);
+        }
+    };
+    for schedule in due {
+        record(&schedule, "wake-triggered");
+        let today = occurrence::today_in_zone(&schedule, to);
+        let success_count = success_count_map
+            .get(&history_key(&schedule))
+            .copied()
+            .unwrap_or(0);
+        if is_schedule_finished(&schedule, today, success_count) {
+            record(&schedule, "skipped-expired");
+            if let Err(err) = scheduler::backend().delete_schedule(&schedule.tool, &schedule.schedule_id) {
+                eprintln!("Failed to delete schedule {}: {}", schedule.schedule_id, err);
+            }
+            continue;
+        }
+        let calendar_ids = schedule.blackout_calendars.clone().unwrap_or_default();
+        if blackout::is_blacked_out(&blackout_calendars, &calendar_ids, today) {
+            record(&schedule, "skipped-blackout");
+            continue;
+        }
 
+        // Runs last until the tool exits, so each one gets its own task and the tick
+        // goes on with the other due schedules
+        let state = state.clone();
+        tauri::async_runtime::spawn(async move {
+            match execute_schedule(&schedule, &state, "").await {
+                Ok(RunOutcome::Succeeded) => {}
+                // Neither a skipped, stopped nor failed run uses up a one-off schedule or a run
+                Ok(RunOutcome::Skipped | RunOutcome::Cancelled) => return,
+                Err(err) => {
+                    eprintln!("Failed to run schedule {}: {}", schedule.schedule_id, err);
+                    return;
+                }
+            }
+            if schedule.schedule_type == "once" {
+                if let Err(err) = scheduler::backend().delete_schedule(&schedule.tool, &schedule.schedule_id) {
+                    eprintln!("Failed to delete schedule {}: {}", schedule.schedule_id, err);
+                }
+            } else if is_schedule_finished(&schedule, today, success_count + 1) {
+                unregister_finished_schedule(&schedule);
+            }
+        });
+    }
     Ok(())
 }
 
@@ -420,6 +833,45 @@ async fn check_iterm_status() -> Result<ITermStatus, String> {
     })
 }
 
+/// Config of the one-off schedule a manual run from the main screen registers
+fn one_off_config(
+    tool: &str,
+    schedule_id: &str,
+    target_directory: &str,
+    command_args: &str,
+    time: (u32, u32),
+    target_date: &str,
+) -> plist_manager::LaunchdConfig {
+    plist_manager::LaunchdConfig {
+        tool: tool.to_string(),
+        schedule_id: schedule_id.to_string(),
+        title: "1回のみの予約実行".to_string(),
+        times: vec![time],
+        target_directory: target_directory.to_string(),
+        command_args: command_args.to_string(),
+        schedule_type: "once".to_string(),
+        interval_value: None,
+        interval_unit: None,
+        active_start: None,
+        active_end: None,
+        start_date: Some(target_date.to_string()),
+        weekdays: None,
+        month_day: None,
+        month_week: None,
+        cron_expression: None,
+        end_date: None,
+        max_runs: None,
+        timezone: None,
+        blackout_calendars: None,
+        jitter_minutes: None,
+        enabled: true,
+        after_tool: None,
+        after_schedule_id: None,
+        after_status: None,
+        modified_by: Some("ui".to_string()),
+    }
+}
+
 #[tauri::command]
 async fn execute_claude_command(
     execution_time: String, // HH:MM format
@@ -472,21 +924,10 @@ async fn execute_claude_command(
     // Additional options from UI are not used in launchd mode for simplicity
 
     // Create launchd config for one-time execution
-    let config = plist_manager::LaunchdConfig {
-        tool: "claude".to_string(),
-        schedule_id: schedule_id.clone(),
-        title: "1回のみの予約実行".to_string(),
-        hour,
-        minute,
-        target_directory: target_directory.clone(),
-        command_args: claude_command.clone(),
-        schedule_type: "once".to_string(),
-        interval_value: None,
-        start_date: Some(target_date.clone()),
-    };
+    let config = one_off_config("claude", &schedule_id, &target_directory, &claude_command, (hour, minute), &target_date);
 
     // Create plist and register with launchd
-    plist_manager::create_plist(&config)?;
+    scheduler::backend().create_schedule(&config)?;
 
     Ok(ExecutionResult {
         status: "scheduled".to_string(),
@@ -549,21 +990,10 @@ async fn execute_codex_command(
     );
 
     // Create launchd config for one-time execution
-    let config = plist_manager::LaunchdConfig {
-        tool: "codex".to_string(),
-        schedule_id: schedule_id.clone(),
-        title: "1回のみの予約実行".to_string(),
-        hour,
-        minute,
-        target_directory: target_directory.clone(),
-        command_args: codex_command.clone(),
-        schedule_type: "once".to_string(),
-        interval_value: None,
-        start_date: Some(target_date.clone()),
-    };
+    let config = one_off_config("codex", &schedule_id, &target_directory, &codex_command, (hour, minute), &target_date);
 
     // Create plist and register with launchd
-    plist_manager::create_plist(&config)?;
+    scheduler::backend().create_schedule(&config)?;
 
     Ok(ExecutionResult {
         status: "scheduled".to_string(),
@@ -627,21 +1057,10 @@ async fn execute_gemini_command(
     );
 
     // Create launchd config for one-time execution
-    let config = plist_manager::LaunchdConfig {
-        tool: "gemini".to_string(),
-        schedule_id: schedule_id.clone(),
-        title: "1回のみの予約実行".to_string(),
-        hour,
-        minute,
-        target_directory: target_directory.clone(),
-        command_args: gemini_command.clone(),
-        schedule_type: "once".to_string(),
-        interval_value: None,
-        start_date: Some(target_date.clone()),
-    };
+    let config = one_off_config("gemini", &schedule_id, &target_directory, &gemini_command, (hour, minute), &target_date);
 
     // Create plist and register with launchd
-    plist_manager::create_plist(&config)?;
+    scheduler::backend().create_schedule(&config)?;
 
     Ok(ExecutionResult {
         status: "scheduled".to_string(),
@@ -663,11 +1082,19 @@ fn stop_execution(tool: String, state: State<'_, AppState>) -> Result<String, St
         _ => return Err("無効なツール指定です".to_string()),
     };
 
-    let mut is_running = tool_state.is_running.lock().unwrap();
-    let mut cancel_flag = tool_state.cancel_flag.lock().unwrap();
-    *is_running = false;
-    *cancel_flag = true;
-    Ok(format!("{}の実行を停止しました", tool))
+    // The run itself clears `is_running` once its tool has stopped
+    *tool_state.cancel_flag.lock().unwrap() = true;
+    Ok(format!("{}の実行を停止しています", tool))
+}
+
+/// Re-register zoned schedules whose local fire times moved with a UTC offset change
+fn refresh_zoned_schedules() -> Result<(), String> {
+    for schedule in scheduler::backend().get_registered_schedules()? {
+        if let Err(err) = scheduler::backend().refresh_zoned_schedule(&schedule) {
+            eprintln!("Failed to refresh schedule {}: {}", schedule.schedule_id, err);
+        }
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -679,128 +1106,421 @@ fn get_running_status(state: State<'_, AppState>) -> Result<std::collections::Ha
     Ok(status)
 }
 
-#[tauri::command]
-fn register_schedule(
+/// Whether `schedule` is chained after the schedule `schedule_id` of `tool`. Chains saved
+/// before the upstream's tool was recorded follow that ID for any tool.
+fn follows(schedule: &RegisteredSchedule, tool: &str, schedule_id: &str) -> bool {
+    schedule.schedule_type == "chain"
+        && schedule.after_schedule_id.as_deref() == Some(schedule_id)
+        && schedule.after_tool.as_deref().is_none_or(|after_tool| after_tool == tool)
+}
+
+/// Upstream tool, upstream schedule ID and status that start a chained schedule
+type ChainTrigger = (Option<String>, Option<String>, Option<String>);
+
+/// Check the trigger of a chained schedule and fill in its default status. The upstream
+/// must exist, and following the chain upwards must not lead back to `schedule`, the
+/// tool and ID of the schedule being edited.
+fn validate_chain_trigger(
+    schedule: Option<(&str, &str)>,
+    after_tool: Option<String>,
+    after_schedule_id: Option<String>,
+    after_status: Option<String>,
+) -> Result<ChainTrigger, String> {
+    let (upstream_tool, upstream_id) = after_tool
+        .filter(|tool| !tool.trim().is_empty())
+        .zip(after_schedule_id.filter(|id| !id.trim().is_empty()))
+        .ok_or("先行スケジュールを選択してください".to_string())?;
+    let status = after_status.unwrap_or_else(|| "success".to_string());
+    if !["success", "failure", "any"].contains(&status.as_str()) {
+        return Err("先行スケジュールの完了条件が正しくありません（success / failure / any）".to_string());
+    }
+
+    let schedules = scheduler::backend().get_registered_schedules()?;
+    if !schedules
+        .iter()
+        .any(|schedule| schedule.tool == upstream_tool && schedule.schedule_id == upstream_id)
+    {
+        return Err(format!("先行スケジュールが見つかりません: {}", upstream_id));
+    }
+    let mut current = Some((upstream_tool.clone(), upstream_id.clone()));
+    // Bounded walk, so a cycle that already exists on disk cannot hang us
+    for _ in 0..=schedules.len() {
+        let Some((tool, id)) = current else {
+            break;
+        };
+        if Some((tool.as_str(), id.as_str())) == schedule {
+            return Err("スケジュールの連鎖が循環しています".to_string());
+        }
+        current = schedules
+            .iter()
+            .find(|schedule| {
+                schedule.tool == tool && schedule.schedule_id == id && schedule.schedule_type == "chain"
+            })
+            .and_then(|chain| {
+                let after_id = chain.after_schedule_id.clone()?;
+                // Older chains name no tool; take the schedule their trigger matches first
+                let after_tool = match &chain.after_tool {
+                    Some(after_tool) => after_tool.clone(),
+                    None => schedules.iter().find(|other| other.schedule_id == after_id)?.tool.clone(),
+                };
+                Some((after_tool, after_id))
+            });
+    }
+    Ok((Some(upstream_tool), Some(upstream_id), Some(status)))
+}
+
+/// Kickstart the enabled schedules chained after the schedule `schedule_id` of `tool`
+/// whose trigger matches how the upstream run ended. Returns the IDs that were started.
+fn start_followups(tool: &str, schedule_id: &str, succeeded: bool) -> Result<Vec<String>, String> {
+    let mut started = Vec::new();
+    for schedule in scheduler::backend().get_registered_schedules()? {
+        if !schedule.enabled || !follows(&schedule, tool, schedule_id) {
+            continue;
+        }
+        let triggered = match schedule.after_status.as_deref().unwrap_or("success") {
+            "any" => true,
+            "failure" => !succeeded,
+            _ => succeeded,
+        };
+        if !triggered {
+            continue;
+        }
+        match scheduler::backend().start_job(&schedule.tool, &schedule.schedule_id) {
+            Ok(()) => started.push(schedule.schedule_id),
+            Err(err) => eprintln!("Failed to start follow-up {}: {}", schedule.schedule_id, err),
+        }
+    }
+    Ok(started)
+}
+
+/// Other schedules for the same tool or directory that fire within the window of `config`.
+/// These are only warnings; a failure to list schedules yields none.
+fn find_schedule_conflicts(
+    config: &LaunchdConfig,
+    conflict_window_minutes: Option<u32>,
+) -> Vec<conflict::ScheduleConflict> {
+    let window = conflict_window_minutes.unwrap_or(DEFAULT_CONFLICT_WINDOW_MINUTES);
+    scheduler::backend().get_registered_schedules()
+        .map(|others| conflict::find_conflicts(&config.to_registered(), &others, window, Utc::now()))
+        .unwrap_or_default()
+}
+
+fn with_conflict_notes(message: String, warnings: &[conflict::ScheduleConflict]) -> String {
+    warnings.iter().fold(message, |message, warning| {
+        format!("{}\n注意: {}", message, warning.describe())
+    })
+}
+
+/// Fields of the schedule form, shared by `register_schedule` and `update_schedule`
+struct ScheduleInput {
     tool: String,
-    execution_time: String,
+    execution_time: Vec<String>,
     target_directory: String,
     command_args: String,
     title: String,
     schedule_type: Option<String>,
     interval_value: Option<u32>,
+    interval_unit: Option<String>,
+    active_start: Option<String>,
+    active_end: Option<String>,
     start_date: Option<String>,
-) -> Result<ScheduleResult, String> {
-    // Parse execution time (HH:MM format)
-    let parts: Vec<&str> = execution_time.split(':').collect();
-    if parts.len() != 2 {
-        return Ok(ScheduleResult {
-            success: false,
-            message: "時刻の形式が正しくありません（HH:MM形式で指定してください）".to_string(),
-            registered_tool: None,
-            schedule_id: None,
-        });
-    }
+    weekdays: Option<Vec<u32>>,
+    month_day: Option<i32>,
+    month_week: Option<i32>,
+    cron_expression: Option<String>,
+    end_date: Option<String>,
+    max_runs: Option<u32>,
+    timezone: Option<String>,
+    blackout_calendars: Option<Vec<String>>,
+    jitter_minutes: Option<u32>,
+    after_tool: Option<String>,
+    after_schedule_id: Option<String>,
+    after_status: Option<String>,
+}
 
-    let hour: u32 = match parts[0].parse() {
-        Ok(h) if h <= 23 => h,
-        _ => {
-            return Ok(ScheduleResult {
-                success: false,
-                message: "時間は0-23の範囲で指定してください".to_string(),
-                registered_tool: None,
-                schedule_id: None,
-            });
-        }
+/// Validate the schedule form and turn it into the config to install. `schedule_id` is
+/// the schedule being edited, or `None` to allocate an ID for a new one.
+fn build_config(input: ScheduleInput, schedule_id: Option<String>) -> Result<LaunchdConfig, String> {
+    let ScheduleInput {
+        tool,
+        execution_time,
+        target_directory,
+        command_args,
+        title,
+        schedule_type,
+        interval_value,
+        interval_unit,
+        active_start,
+        active_end,
+        start_date,
+        weekdays,
+        month_day,
+        month_week,
+        cron_expression,
+        end_date,
+        max_runs,
+        timezone,
+        blackout_calendars,
+        jitter_minutes,
+        after_tool,
+        after_schedule_id,
+        after_status,
+    } = input;
+
+    let sched_type = schedule_type.unwrap_or_else(|| "daily".to_string());
+    let sub_daily_minutes = if sched_type == "interval" {
+        plist_manager::sub_daily_interval_minutes(&interval_unit, interval_value)
+    } else {
+        None
     };
 
-    let minute: u32 = match parts[1].parse() {
-        Ok(m) if m <= 59 => m,
-        _ => {
-            return Ok(ScheduleResult {
-                success: false,
-                message: "分は0-59の範囲で指定してください".to_string(),
-                registered_tool: None,
-                schedule_id: None,
-            });
-        }
+    // Cron schedules carry their fire times in the expression itself, sub-daily
+    // intervals fire on launchd's timer and chained schedules after their upstream
+    let times = if sched_type == "cron" || sched_type == "chain" || sub_daily_minutes.is_some() {
+        Vec::new()
+    } else {
+        parse_execution_times(&execution_time)?
     };
 
     // Validate tool
     if !["claude", "codex", "gemini"].contains(&tool.as_str()) {
-        return Ok(ScheduleResult {
-            success: false,
-            message: "無効なツール指定です".to_string(),
-            registered_tool: None,
-            schedule_id: None,
-        });
+        return Err("無効なツール指定です".to_string());
     }
 
     if command_args.trim().is_empty() {
-        return Ok(ScheduleResult {
-            success: false,
-            message: "スケジュール命令を入力してください".to_string(),
-            registered_tool: None,
-            schedule_id: None,
-        });
+        return Err("スケジュール命令を入力してください".to_string());
     }
 
-    let sched_type = schedule_type.unwrap_or_else(|| "daily".to_string());
-
     // Basic validation for interval/weekly
-    if sched_type == "weekly" && start_date.is_none() {
-         return Ok(ScheduleResult {
-            success: false,
-            message: "毎週実行の場合は開始日を指定してください（曜日決定のため）".to_string(),
-            registered_tool: None,
-            schedule_id: None,
-        });
+    let weekdays = normalize_weekdays(weekdays);
+    if sched_type == "weekly" && weekdays.is_none() && start_date.is_none() {
+        return Err("毎週実行の場合は曜日を1つ以上指定してください".to_string());
     }
 
     if sched_type == "interval" {
-        if interval_value.is_none() || start_date.is_none() {
-            return Ok(ScheduleResult {
-                success: false,
-                message: "間隔実行の場合は間隔（日）と開始日を指定してください".to_string(),
-                registered_tool: None,
-                schedule_id: None,
-            });
+        let interval_unit = interval_unit.as_deref().unwrap_or("days");
+        if !["days", "hours", "minutes"].contains(&interval_unit) {
+            return Err("間隔の単位が正しくありません".to_string());
+        }
+        if interval_value == Some(0) {
+            return Err("間隔実行の場合は1以上の間隔を指定してください".to_string());
+        }
+        if interval_unit == "days" && (interval_value.is_none() || start_date.is_none()) {
+            return Err("間隔実行の場合は間隔（日）と開始日を指定してください".to_string());
+        }
+        if interval_unit != "days" && sub_daily_minutes.unwrap_or(0) == 0 {
+            return Err("間隔実行の場合は1以上の間隔を指定してください".to_string());
         }
     }
 
-    let now = chrono::Local::now();
-    let schedule_id = format!(
-        "{}{:03}",
-        now.format("%Y%m%d%H%M%S"),
-        now.timestamp_subsec_millis()
-    );
+    // The active window only applies to sub-daily intervals and needs both ends
+    let (active_start, active_end) = if sub_daily_minutes.is_some() {
+        (
+            active_start.filter(|time| !time.trim().is_empty()),
+            active_end.filter(|time| !time.trim().is_empty()),
+        )
+    } else {
+        (None, None)
+    };
+    let window_valid = match (&active_start, &active_end) {
+        (Some(start), Some(end)) => match (parse_schedule_time(start), parse_schedule_time(end)) {
+            (Some(start), Some(end)) => start != end,
+            _ => false,
+        },
+        (None, None) => true,
+        _ => false,
+    };
+    if !window_valid {
+        return Err("実行時間帯は開始と終了を異なる時刻（HH:MM）で指定してください".to_string());
+    }
+
+    if sched_type == "monthly" {
+        let valid = match (month_day, month_week) {
+            (Some(day), None) => day == -1 || (1..=31).contains(&day),
+            (None, Some(week)) => {
+                (week == -1 || (1..=5).contains(&week))
+                    && weekdays.as_ref().map(|w| w.len()) == Some(1)
+            }
+            _ => false,
+        };
+        if !valid {
+            return Err("毎月実行の場合は日付（1-31または月末）か、第n曜日（曜日を1つ）を指定してください".to_string());
+        }
+    }
+
+    if sched_type == "cron" {
+        let expression = cron_expression.as_deref().unwrap_or_default();
+        if let Err(e) = cron::CronSchedule::parse(expression).and_then(|c| c.calendar_intervals()) {
+            return Err(format!("cron式が正しくありません: {}", e));
+        }
+    }
 
-    // Create LaunchdConfig and generate plist
-    let config = LaunchdConfig {
-        tool: tool.clone(),
-        schedule_id: schedule_id.clone(),
+    let end_date = end_date.filter(|date| !date.trim().is_empty());
+    if let Some(date) = &end_date {
+        if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            return Err("終了日の形式が正しくありません（YYYY-MM-DD）".to_string());
+        }
+    }
+
+    if max_runs == Some(0) {
+        return Err("最大実行回数は1以上を指定してください".to_string());
+    }
+
+    let timezone = timezone.filter(|tz| !tz.trim().is_empty());
+    if timezone.is_some() && occurrence::parse_timezone(&timezone).is_none() {
+        return Err("タイムゾーンが正しくありません（例: America/New_York）".to_string());
+    }
+
+    let jitter_minutes = jitter_minutes.filter(|minutes| *minutes > 0);
+    if let Some(minutes) = jitter_minutes {
+        if minutes > MAX_JITTER_MINUTES {
+            return Err(format!("ジッターは{}分以内で指定してください", MAX_JITTER_MINUTES));
+        }
+        // A delayed run must finish waiting before launchd fires the next slot
+        if sub_daily_minutes.is_some_and(|interval| minutes >= interval) {
+            return Err("ジッターは実行間隔より短くしてください".to_string());
+        }
+    }
+
+    let (after_tool, after_schedule_id, after_status) = if sched_type == "chain" {
+        let schedule = schedule_id.as_deref().map(|schedule_id| (tool.as_str(), schedule_id));
+        validate_chain_trigger(schedule, after_tool, after_schedule_id, after_status)?
+    } else {
+        (None, None, None)
+    };
+
+    // Editing keeps a paused schedule paused
+    let enabled = schedule_id.as_ref().is_none_or(|schedule_id| {
+        scheduler::backend()
+            .get_registered_schedule(&tool, schedule_id)
+            .ok()
+            .flatten()
+            .is_none_or(|schedule| schedule.enabled)
+    });
+    let schedule_id = schedule_id.unwrap_or_else(|| {
+        let now = chrono::Local::now();
+        format!("{}{:03}", now.format("%Y%m%d%H%M%S"), now.timestamp_subsec_millis())
+    });
+
+    Ok(LaunchdConfig {
+        tool,
+        schedule_id,
         title,
-        hour,
-        minute,
+        times,
         target_directory,
         command_args,
-        schedule_type: sched_type.clone(),
+        schedule_type: sched_type,
         interval_value,
+        interval_unit,
+        active_start,
+        active_end,
         start_date,
+        weekdays,
+        month_day,
+        month_week,
+        cron_expression,
+        end_date,
+        max_runs,
+        timezone,
+        blackout_calendars: blackout_calendars.filter(|ids| !ids.is_empty()),
+        jitter_minutes,
+        enabled,
+        after_tool,
+        after_schedule_id,
+        after_status,
+        modified_by: Some("ui".to_string()),
+    })
+}
+
+#[tauri::command]
+fn register_schedule(
+    tool: String,
+    execution_time: Vec<String>,
+    target_directory: String,
+    command_args: String,
+    title: String,
+    schedule_type: Option<String>,
+    interval_value: Option<u32>,
+    interval_unit: Option<String>,
+    active_start: Option<String>,
+    active_end: Option<String>,
+    start_date: Option<String>,
+    weekdays: Option<Vec<u32>>,
+    month_day: Option<i32>,
+    month_week: Option<i32>,
+    cron_expression: Option<String>,
+    end_date: Option<String>,
+    max_runs: Option<u32>,
+    timezone: Option<String>,
+    blackout_calendars: Option<Vec<String>>,
+    jitter_minutes: Option<u32>,
+    after_tool: Option<String>,
+    after_schedule_id: Option<String>,
+    after_status: Option<String>,
+    conflict_window_minutes: Option<u32>,
+) -> Result<ScheduleResult, String> {
+    let input = ScheduleInput {
+        tool,
+        execution_time,
+        target_directory,
+        command_args,
+        title,
+        schedule_type,
+        interval_value,
+        interval_unit,
+        active_start,
+        active_end,
+        start_date,
+        weekdays,
+        month_day,
+        month_week,
+        cron_expression,
+        end_date,
+        max_runs,
+        timezone,
+        blackout_calendars,
+        jitter_minutes,
+        after_tool,
+        after_schedule_id,
+        after_status,
     };
+    let config = match build_config(input, None) {
+        Ok(config) => config,
+        Err(message) => {
+            return Ok(ScheduleResult {
+                success: false,
+                message,
+                registered_tool: None,
+                schedule_id: None,
+                warnings: None,
+            });
+        }
+    };
+    let warnings = find_schedule_conflicts(&config, conflict_window_minutes);
 
-    match plist_manager::create_plist(&config) {
+    match scheduler::backend().create_schedule(&config) {
         Ok(_msg) => {
-            let msg = match sched_type.as_str() {
-                "daily" => format!("スケジュール登録成功: 毎日 {}:{:02}", hour, minute),
-                "weekly" => format!("スケジュール登録成功: 毎週 {}:{:02}", hour, minute),
-                "interval" => format!("スケジュール登録成功: {}日ごと {}:{:02}", interval_value.unwrap_or(0), hour, minute),
-                _ => format!("スケジュール登録成功: {}:{:02}", hour, minute),
+            let times = &config.times;
+            let interval_value = config.interval_value;
+            let msg = match config.schedule_type.as_str() {
+                "daily" => format!("スケジュール登録成功: 毎日 {}", format_execution_times(times)),
+                "weekly" => format!("スケジュール登録成功: 毎週 {}", format_execution_times(times)),
+                "monthly" => format!("スケジュール登録成功: 毎月 {}", format_execution_times(times)),
+                "interval" => match config.interval_unit.as_deref() {
+                    Some("hours") => format!("スケジュール登録成功: {}時間ごと", interval_value.unwrap_or(0)),
+                    Some("minutes") => format!("スケジュール登録成功: {}分ごと", interval_value.unwrap_or(0)),
+                    _ => format!("スケジュール登録成功: {}日ごと {}", interval_value.unwrap_or(0), format_execution_times(times)),
+                },
+                "cron" => format!("スケジュール登録成功: cron {}", config.cron_expression.as_deref().unwrap_or_default()),
+                "chain" => "スケジュール登録成功: 先行スケジュールの完了後に実行".to_string(),
+                _ => format!("スケジュール登録成功: {}", format_execution_times(times)),
             };
             Ok(ScheduleResult {
                 success: true,
-                message: msg,
-                registered_tool: Some(tool),
-                schedule_id: Some(schedule_id),
+                message: with_conflict_notes(msg, &warnings),
+                registered_tool: Some(config.tool),
+                schedule_id: Some(config.schedule_id),
+                warnings: Some(warnings).filter(|warnings| !warnings.is_empty()),
             })
         },
         Err(e) => Ok(ScheduleResult {
@@ -808,6 +1528,7 @@ fn register_schedule(
             message: format!("スケジュール登録エラー: {}", e),
             registered_tool: None,
             schedule_id: None,
+            warnings: None,
         }),
     }
 }
@@ -821,153 +1542,343 @@ fn unregister_schedule(tool: String, schedule_id: String) -> Result<ScheduleResu
             message: "無効なツール指定です".to_string(),
             registered_tool: None,
             schedule_id: None,
+            warnings: None,
         });
     }
 
-    match plist_manager::delete_plist(&tool, &schedule_id) {
+    match scheduler::backend().delete_schedule(&tool, &schedule_id) {
         Ok(_msg) => Ok(ScheduleResult {
             success: true,
             message: "スケジュール削除成功".to_string(),
             registered_tool: Some(tool),
             schedule_id: Some(schedule_id),
+            warnings: None,
         }),
         Err(e) => Ok(ScheduleResult {
             success: false,
             message: format!("スケジュール削除エラー: {}", e),
             registered_tool: None,
             schedule_id: None,
+            warnings: None,
         }),
     }
 }
 
 #[tauri::command]
-fn update_schedule(
-    tool: String,
-    schedule_id: String,
-    execution_time: String,
-    target_directory: String,
-    command_args: String,
-    title: String,
-    schedule_type: Option<String>,
-    interval_value: Option<u32>,
-    start_date: Option<String>,
-) -> Result<ScheduleResult, String> {
-    let parts: Vec<&str> = execution_time.split(':').collect();
-    if parts.len() != 2 {
+fn pause_schedule(tool: String, schedule_id: String) -> Result<ScheduleResult, String> {
+    if !["claude", "codex", "gemini"].contains(&tool.as_str()) {
         return Ok(ScheduleResult {
             success: false,
-            message: "時刻の形式が正しくありません（HH:MM形式で指定してください）".to_string(),
+            message: "無効なツール指定です".to_string(),
             registered_tool: None,
             schedule_id: None,
+            warnings: None,
         });
     }
 
-    let hour: u32 = match parts[0].parse() {
-        Ok(h) if h <= 23 => h,
-        _ => {
-            return Ok(ScheduleResult {
-                success: false,
-                message: "時間は0-23の範囲で指定してください".to_string(),
-                registered_tool: None,
-                schedule_id: None,
-            });
-        }
-    };
-
-    let minute: u32 = match parts[1].parse() {
-        Ok(m) if m <= 59 => m,
-        _ => {
-            return Ok(ScheduleResult {
-                success: false,
-                message: "分は0-59の範囲で指定してください".to_string(),
-                registered_tool: None,
-                schedule_id: None,
-            });
-        }
-    };
-
-    if !["claude", "codex", "gemini"].contains(&tool.as_str()) {
-        return Ok(ScheduleResult {
+    match scheduler::backend().set_schedule_enabled(&tool, &schedule_id, false, "ui") {
+        Ok(()) => Ok(ScheduleResult {
+            success: true,
+            message: "スケジュール一時停止成功".to_string(),
+            registered_tool: Some(tool),
+            schedule_id: Some(schedule_id),
+            warnings: None,
+        }),
+        Err(e) => Ok(ScheduleResult {
             success: false,
-            message: "無効なツール指定です".to_string(),
+            message: format!("スケジュール一時停止エラー: {}", e),
             registered_tool: None,
             schedule_id: None,
-        });
+            warnings: None,
+        }),
     }
+}
 
-    if command_args.trim().is_empty() {
+#[tauri::command]
+fn resume_schedule(tool: String, schedule_id: String) -> Result<ScheduleResult, String> {
+    if !["claude", "codex", "gemini"].contains(&tool.as_str()) {
         return Ok(ScheduleResult {
             success: false,
-            message: "スケジュール命令を入力してください".to_string(),
+            message: "無効なツール指定です".to_string(),
             registered_tool: None,
             schedule_id: None,
+            warnings: None,
         });
     }
 
-    let sched_type = schedule_type.unwrap_or_else(|| "daily".to_string());
-    if sched_type == "weekly" && start_date.is_none() {
-        return Ok(ScheduleResult {
+    match scheduler::backend().set_schedule_enabled(&tool, &schedule_id, true, "ui") {
+        Ok(()) => Ok(ScheduleResult {
+            success: true,
+            message: "スケジュール再開成功".to_string(),
+            registered_tool: Some(tool),
+            schedule_id: Some(schedule_id),
+            warnings: None,
+        }),
+        Err(e) => Ok(ScheduleResult {
             success: false,
-            message: "毎週実行の場合は開始日を指定してください（曜日決定のため）".to_string(),
+            message: format!("スケジュール再開エラー: {}", e),
             registered_tool: None,
             schedule_id: None,
-        });
+            warnings: None,
+        }),
     }
+}
 
-    if sched_type == "interval" {
-        if interval_value.is_none() || start_date.is_none() {
+#[tauri::command]
+fn update_schedule(
+    tool: String,
+    schedule_id: String,
+    execution_time: Vec<String>,
+    target_directory: String,
+    command_args: String,
+    title: String,
+    schedule_type: Option<String>,
+    interval_value: Option<u32>,
+    interval_unit: Option<String>,
+    active_start: Option<String>,
+    active_end: Option<String>,
+    start_date: Option<String>,
+    weekdays: Option<Vec<u32>>,
+    month_day: Option<i32>,
+    month_week: Option<i32>,
+    cron_expression: Option<String>,
+    end_date: Option<String>,
+    max_runs: Option<u32>,
+    timezone: Option<String>,
+    blackout_calendars: Option<Vec<String>>,
+    jitter_minutes: Option<u32>,
+    after_tool: Option<String>,
+    after_schedule_id: Option<String>,
+    after_status: Option<String>,
+    conflict_window_minutes: Option<u32>,
+) -> Result<ScheduleResult, String> {
+    let input = ScheduleInput {
+        tool,
+        execution_time,
+        target_directory,
+        command_args,
+        title,
+        schedule_type,
+        interval_value,
+        interval_unit,
+        active_start,
+        active_end,
+        start_date,
+        weekdays,
+        month_day,
+        month_week,
+        cron_expression,
+        end_date,
+        max_runs,
+        timezone,
+        blackout_calendars,
+        jitter_minutes,
+        after_tool,
+        after_schedule_id,
+        after_status,
+    };
+    let config = match build_config(input, Some(schedule_id)) {
+        Ok(config) => config,
+        Err(message) => {
             return Ok(ScheduleResult {
                 success: false,
-                message: "間隔実行の場合は間隔（日）と開始日を指定してください".to_string(),
+                message,
                 registered_tool: None,
                 schedule_id: None,
+                warnings: None,
             });
         }
-    }
-
-    let config = LaunchdConfig {
-        tool: tool.clone(),
-        schedule_id: schedule_id.clone(),
-        title,
-        hour,
-        minute,
-        target_directory,
-        command_args,
-        schedule_type: sched_type.clone(),
-        interval_value,
-        start_date,
     };
+    let warnings = find_schedule_conflicts(&config, conflict_window_minutes);
 
-    match plist_manager::create_plist(&config) {
+    match scheduler::backend().create_schedule(&config) {
         Ok(_msg) => Ok(ScheduleResult {
             success: true,
-            message: "スケジュール更新成功".to_string(),
-            registered_tool: Some(tool),
-            schedule_id: Some(schedule_id),
+            message: with_conflict_notes("スケジュール更新成功".to_string(), &warnings),
+            registered_tool: Some(config.tool),
+            schedule_id: Some(config.schedule_id),
+            warnings: Some(warnings).filter(|warnings| !warnings.is_empty()),
         }),
         Err(e) => Ok(ScheduleResult {
             success: false,
             message: format!("スケジュール更新エラー: {}", e),
             registered_tool: None,
             schedule_id: None,
+            warnings: None,
         }),
     }
 }
 
 #[tauri::command]
 fn get_registered_schedules() -> Result<Vec<RegisteredSchedule>, String> {
-    plist_manager::get_registered_schedules()
+    scheduler::backend().get_registered_schedules()
+}
+
+/// Compare the installed jobs with the registered schedules; with `repair`, rewrite the
+/// drifted jobs and remove the ones left by deleted schedules
+#[tauri::command]
+fn reconcile_schedules(repair: bool) -> Result<Vec<scheduler::ScheduleDrift>, String> {
+    scheduler::backend().reconcile_schedules(repair)
+}
+
+/// Upcoming fire times (RFC 3339, in the schedule's zone), skipping blackout days and
+/// stopping at the end date or the remaining run budget
+#[tauri::command]
+fn get_next_occurrences(tool: String, schedule_id: String, count: u32) -> Result<Vec<String>, String> {
+    let schedule = scheduler::backend().get_registered_schedule(&tool, &schedule_id)?
+        .ok_or_else(|| format!("スケジュールが見つかりません: {}", schedule_id))?;
+    if !schedule.enabled {
+        return Ok(Vec::new());
+    }
+
+    let mut count = count.min(MAX_NEXT_OCCURRENCES);
+    if let Some(max_runs) = schedule.max_runs {
+        let success_count = load_success_count_map()?
+            .get(&history_key(&schedule))
+            .copied()
+            .unwrap_or(0);
+        count = count.min(max_runs.saturating_sub(success_count));
+    }
+
+    let blackout_calendars = blackout::load_calendars().unwrap_or_default();
+    let calendar_ids = schedule.blackout_calendars.clone().unwrap_or_default();
+    let occurrences = occurrence::next_scheduled_in_zone(&schedule, Utc::now(), count as usize, |date| {
+        blackout::is_blacked_out(&blackout_calendars, &calendar_ids, date)
+    });
+    Ok(occurrences.iter().map(|time| time.to_rfc3339()).collect())
+}
+
+/// Outcome of one occurrence from the history entries written between it and the
+/// schedule's next occurrence. A later catch-up success wins over an earlier failure.
+fn occurrence_outcome(statuses: &[&str]) -> Option<(&'static str, String)> {
+    let find = |matches: fn(&str) -> bool| statuses.iter().find(|status| matches(status));
+    if let Some(status) = find(|status| status == "success" || status == "catchup-success") {
+        return Some(("ran", status.to_string()));
+    }
+    if let Some(status) = find(|status| status == "failure" || status == "catchup-failure") {
+        return Some(("failed", status.to_string()));
+    }
+    // A stopped run did not get to finish, like a skipped one
+    if let Some(status) = find(|status| {
+        status.starts_with("skipped") || status.starts_with("catchup-skipped") || status.ends_with("cancelled")
+    }) {
+        return Some(("skipped", status.to_string()));
+    }
+    None
 }
 
+/// Every expected run of every registered schedule between two local dates (inclusive),
+/// with past runs resolved against the history
 #[tauri::command]
-fn get_schedule_history(schedule_id: String) -> Result<Vec<ScheduleHistoryEntry>, String> {
+fn get_schedule_calendar(start_date: String, end_date: String) -> Result<Vec<CalendarOccurrence>, String> {
+    let (Ok(start), Ok(end)) = (
+        NaiveDate::parse_from_str(&start_date, "%Y-%m-%d"),
+        NaiveDate::parse_from_str(&end_date, "%Y-%m-%d"),
+    ) else {
+        return Err("日付の形式が正しくありません（YYYY-MM-DD）".to_string());
+    };
+    if end < start {
+        return Err("終了日は開始日以降を指定してください".to_string());
+    }
+    if (end - start).num_days() >= MAX_CALENDAR_RANGE_DAYS {
+        return Err(format!("期間は{}日以内で指定してください", MAX_CALENDAR_RANGE_DAYS));
+    }
+    let local_midnight = |date: NaiveDate| {
+        chrono::Local
+            .from_local_datetime(&date.and_time(chrono::NaiveTime::MIN))
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok_or_else(|| format!("日付を解釈できません: {}", date))
+    };
+    let from = local_midnight(start)?;
+    let to = local_midnight(end.succ_opt().ok_or("日付が範囲外です".to_string())?)? - chrono::Duration::seconds(1);
+
+    let now = Utc::now();
+    let mut history: HashMap<HistoryKey, Vec<(DateTime<Utc>, String)>> = HashMap::new();
+    for entry in read_history_entries()? {
+        if let Ok(timestamp) = DateTime::parse_from_rfc3339(&entry.timestamp) {
+            history
+                .entry((entry.tool, entry.schedule_id))
+                .or_default()
+                .push((timestamp.with_timezone(&Utc), entry.status));
+        }
+    }
+    let success_count_map = load_success_count_map()?;
+    let blackout_calendars = blackout::load_calendars().unwrap_or_default();
+
+    let mut occurrences: Vec<(DateTime<chrono::FixedOffset>, CalendarOccurrence)> = Vec::new();
+    for schedule in scheduler::backend().get_registered_schedules()? {
+        let times = occurrence::scheduled_between_in_zone(&schedule, from, to);
+        let Some(last) = times.last() else {
+            continue;
+        };
+        // Entries written after the last occurrence in range belong to it until the next one
+        let window_end = occurrence::next_scheduled_in_zone(&schedule, last.with_timezone(&Utc), 1, |_| false)
+            .first()
+            .map(|time| time.with_timezone(&Utc));
+        let entries = history.get(&history_key(&schedule)).map(Vec::as_slice).unwrap_or_default();
+        let calendar_ids = schedule.blackout_calendars.clone().unwrap_or_default();
+        let mut remaining_runs = schedule.max_runs.map(|max_runs| {
+            max_runs.saturating_sub(success_count_map.get(&history_key(&schedule)).copied().unwrap_or(0))
+        });
+
+        let next_times: Vec<Option<DateTime<Utc>>> = times
+            .iter()
+            .skip(1)
+            .map(|time| Some(time.with_timezone(&Utc)))
+            .chain(std::iter::once(window_end))
+            .collect();
+        for (time, next_time) in times.into_iter().zip(next_times) {
+            let scheduled_utc = time.with_timezone(&Utc);
+            let statuses: Vec<&str> = entries
+                .iter()
+                .filter(|(timestamp, _)| {
+                    *timestamp >= scheduled_utc && next_time.is_none_or(|next| *timestamp < next)
+                })
+                .map(|(_, status)| status.as_str())
+                .collect();
+            let blacked_out = blackout::is_blacked_out(&blackout_calendars, &calendar_ids, time.date_naive());
+
+            let (status, history_status) = match occurrence_outcome(&statuses) {
+                Some((status, history_status)) => (status, Some(history_status)),
+                // A paused schedule has nothing pending and missed nothing
+                None if !schedule.enabled => continue,
+                None if blacked_out => ("skipped", None),
+                // Still waiting for this run (or a catch-up) to report back
+                None if scheduled_utc > now || next_time.is_none_or(|next| next > now) => {
+                    if remaining_runs == Some(0) {
+                        break;
+                    }
+                    remaining_runs = remaining_runs.map(|runs| runs - 1);
+                    ("scheduled", None)
+                }
+                None => ("missed", None),
+            };
+            occurrences.push((
+                time,
+                CalendarOccurrence {
+                    schedule_id: schedule.schedule_id.clone(),
+                    tool: schedule.tool.clone(),
+                    title: schedule.title.clone(),
+                    scheduled_at: time.to_rfc3339(),
+                    status: status.to_string(),
+                    history_status,
+                },
+            ));
+        }
+    }
+
+    occurrences.sort_by_key(|(time, _)| *time);
+    Ok(occurrences.into_iter().map(|(_, occurrence)| occurrence).collect())
+}
+
+#[tauri::command]
+fn get_schedule_history(tool: String, schedule_id: String) -> Result<Vec<ScheduleHistoryEntry>, String> {
     if schedule_id.trim().is_empty() {
         return Ok(Vec::new());
     }
 
-    let config_dir = dirs::config_dir()
-        .ok_or("Could not determine config directory".to_string())?
-        .join("tauri-cli-scheduler");
+    let config_dir = plist_manager::get_config_dir()?;
     let history_path = config_dir.join("schedule-history.jsonl");
 
     if !history_path.exists() {
@@ -988,7 +1899,7 @@ fn get_schedule_history(schedule_id: String) -> Result<Vec<ScheduleHistoryEntry>
             Ok(val) => val,
             Err(_) => continue,
         };
-        if entry.schedule_id == schedule_id {
+        if entry.tool == tool && entry.schedule_id == schedule_id {
             entries.push(entry);
         }
     }
@@ -1002,6 +1913,240 @@ fn get_schedule_history(schedule_id: String) -> Result<Vec<ScheduleHistoryEntry>
     Ok(entries)
 }
 
+#[tauri::command]
+fn get_blackout_calendars() -> Result<Vec<blackout::BlackoutCalendar>, String> {
+    blackout::load_calendars()
+}
+
+#[tauri::command]
+fn save_blackout_calendar(
+    calendar: blackout::BlackoutCalendar,
+) -> Result<blackout::BlackoutCalendar, String> {
+    let mut calendar = calendar;
+    if calendar.id.trim().is_empty() {
+        calendar.id = new_calendar_id();
+    }
+    calendar.validate()?;
+
+    let mut calendars = blackout::load_calendars()?;
+    match calendars.iter_mut().find(|existing| existing.id == calendar.id) {
+        Some(existing) => *existing = calendar.clone(),
+        None => calendars.push(calendar.clone()),
+    }
+    blackout::save_calendars(&calendars)?;
+    Ok(calendar)
+}
+
+#[tauri::command]
+fn delete_blackout_calendar(calendar_id: String) -> Result<(), String> {
+    let mut calendars = blackout::load_calendars()?;
+    calendars.retain(|calendar| calendar.id != calendar_id);
+    blackout::save_calendars(&calendars)
+}
+
+#[tauri::command]
+fn import_blackout_ics(name: String, path: String) -> Result<blackout::IcsImport, String> {
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("icsファイルの読み込みに失敗しました: {}", e))?;
+    let name = if name.trim().is_empty() {
+        std::path::Path::new(&path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "インポートしたカレンダー".to_string())
+    } else {
+        name
+    };
+    let import = blackout::parse_ics(new_calendar_id(), name, &content)?;
+
+    let mut calendars = blackout::load_calendars()?;
+    calendars.push(import.calendar.clone());
+    blackout::save_calendars(&calendars)?;
+    Ok(import)
+}
+
+fn new_calendar_id() -> String {
+    let now = chrono::Local::now();
+    format!("{}{:03}", now.format("%Y%m%d%H%M%S"), now.timestamp_subsec_millis())
+}
+
+/// Entry point for `check-due`, run by the run scripts before executing a schedule.
+/// Reads TOOL and SCHEDULE_ID from the environment and exits 0 when the schedule is due
+/// today, 1 when it is not, 2 when the schedule cannot be evaluated, and 3 when it is
+/// due but today is excluded by a blackout calendar.
+pub fn check_due() -> i32 {
+    let (tool, schedule_id) = match (std::env::var("TOOL"), std::env::var("SCHEDULE_ID")) {
+        (Ok(tool), Ok(schedule_id)) => (tool, schedule_id),
+        _ => {
+            eprintln!("TOOL and SCHEDULE_ID must be set");
+            return 2;
+        }
+    };
+    let schedule = match scheduler::backend().get_registered_schedule(&tool, &schedule_id) {
+        Ok(Some(schedule)) => schedule,
+        Ok(None) => {
+            eprintln!("Schedule not found: {}.{}", tool, schedule_id);
+            return 2;
+        }
+        Err(err) => {
+            eprintln!("Failed to load schedule: {}", err);
+            return 2;
+        }
+    };
+
+    let now = Utc::now();
+    if !is_due_now(&schedule, now) {
+        return 1;
+    }
+
+    let today = occurrence::today_in_zone(&schedule, now);
+    if let Some(calendar_ids) = &schedule.blackout_calendars {
+        let calendars = match blackout::load_calendars() {
+            Ok(calendars) => calendars,
+            Err(err) => {
+                eprintln!("Failed to load blackout calendars: {}", err);
+                return 2;
+            }
+        };
+        if blackout::is_blacked_out(&calendars, calendar_ids, today) {
+            println!("Schedule {} is blacked out on {}", schedule.schedule_id, today);
+            return 3;
+        }
+    }
+    0
+}
+
+/// Entry point of the `run-followups <success|failure>` subcommand, called by the run
+/// scripts once a schedule's run has ended. Exit codes: 0 = done, 2 = error.
+pub fn run_followups(status: &str) -> i32 {
+    let (tool, schedule_id) = match (std::env::var("TOOL"), std::env::var("SCHEDULE_ID")) {
+        (Ok(tool), Ok(schedule_id)) => (tool, schedule_id),
+        _ => {
+            eprintln!("TOOL and SCHEDULE_ID must be set");
+            return 2;
+        }
+    };
+    let succeeded = match status {
+        "success" => true,
+        "failure" => false,
+        _ => {
+            eprintln!("Unknown run status: {}", status);
+            return 2;
+        }
+    };
+    match start_followups(&tool, &schedule_id, succeeded) {
+        Ok(started) => {
+            for id in started {
+                println!("Started follow-up schedule {}", id);
+            }
+            0
+        }
+        Err(err) => {
+            eprintln!("Failed to start follow-up schedules: {}", err);
+            2
+        }
+    }
+}
+
+/// Entry point of the `schedule-env` subcommand, run by the run scripts since jobs only
+/// name their schedule. Prints the schedule's settings from the store as `export` lines
+/// for the script to evaluate. Exit codes: 0 = printed, 2 = error.
+pub fn schedule_env() -> i32 {
+    let (tool, schedule_id) = match (std::env::var("TOOL"), std::env::var("SCHEDULE_ID")) {
+        (Ok(tool), Ok(schedule_id)) => (tool, schedule_id),
+        _ => {
+            eprintln!("TOOL and SCHEDULE_ID must be set");
+            return 2;
+        }
+    };
+    let config = match scheduler::backend().get_registered_schedule(&tool, &schedule_id) {
+        Ok(Some(schedule)) => match LaunchdConfig::from_registered(&schedule) {
+            Ok(config) => config,
+            Err(err) => {
+                eprintln!("Failed to load schedule: {}", err);
+                return 2;
+            }
+        },
+        Ok(None) => {
+            eprintln!("Schedule not found: {}.{}", tool, schedule_id);
+            return 2;
+        }
+        Err(err) => {
+            eprintln!("Failed to load schedule: {}", err);
+            return 2;
+        }
+    };
+    print!("{}", plist_manager::shell_exports(&plist_manager::schedule_environment(&config)));
+    0
+}
+
+/// Entry point of the `unregister` subcommand, run by the run scripts when a schedule has
+/// finished, so its job and its store entry go away together. Exit codes: 0 = done,
+/// 2 = error.
+pub fn unregister_self() -> i32 {
+    let (tool, schedule_id) = match (std::env::var("TOOL"), std::env::var("SCHEDULE_ID")) {
+        (Ok(tool), Ok(schedule_id)) => (tool, schedule_id),
+        _ => {
+            eprintln!("TOOL and SCHEDULE_ID must be set");
+            return 2;
+        }
+    };
+    match scheduler::backend().delete_schedule(&tool, &schedule_id) {
+        Ok(message) => {
+            println!("{}", message);
+            0
+        }
+        Err(err) => {
+            eprintln!("Failed to unregister schedule: {}", err);
+            2
+        }
+    }
+}
+
+fn is_due_now(schedule: &RegisteredSchedule, now: DateTime<Utc>) -> bool {
+    let schedule_id = &schedule.schedule_id;
+    let today = occurrence::today_in_zone(schedule, now);
+    let sub_daily = schedule.schedule_type == "interval"
+        && plist_manager::sub_daily_interval_minutes(&schedule.interval_unit, schedule.interval_value)
+            .is_some();
+    // Chained schedules have no slots of their own; their upstream decided the timing
+    if schedule.timezone.is_none() || sub_daily || schedule.schedule_type == "chain" {
+        return if occurrence::occurs_on(schedule, today) {
+            println!("Schedule {} is due on {}", schedule_id, today);
+            true
+        } else {
+            println!("Schedule {} is not due on {}", schedule_id, today);
+            false
+        };
+    }
+
+    // Zoned schedules fire daily at translated local times, so run only when the latest
+    // slot in the schedule's zone has not been handled yet
+    let Some(last_scheduled) = occurrence::last_scheduled_in_zone(schedule, now) else {
+        println!("Schedule {} has no occurrence yet", schedule_id);
+        return false;
+    };
+    let last_run = read_history_entries()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| {
+            entry.tool == schedule.tool && entry.schedule_id == *schedule_id && is_run_status(&entry.status)
+        })
+        .filter_map(|entry| DateTime::parse_from_rfc3339(&entry.timestamp).ok())
+        .max();
+    if last_run.is_some_and(|last_run| last_run >= last_scheduled) {
+        println!("Schedule {} already ran for {}", schedule_id, last_scheduled);
+        false
+    } else {
+        println!("Schedule {} is due for {}", schedule_id, last_scheduled);
+        true
+    }
+}
+
+/// History statuses recorded for an actual execution attempt
+fn is_run_status(status: &str) -> bool {
+    matches!(status, "success" | "failure" | "catchup-success" | "catchup-failure")
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -1022,12 +2167,45 @@ pub fn run() {
             },
         })
         .setup(|app| {
+            // Schedules registered before the schedule store move into it once, before
+            // anything reads them
+            match schedule_store::migrate() {
+                Ok(0) => {}
+                Ok(count) => println!("Moved {} schedules into the schedule store", count),
+                Err(err) => eprintln!("Failed to migrate schedules: {}", err),
+            }
+            // ...and so do the single-schedule plists of the oldest versions, which get an
+            // ID of their own instead of "legacy"
+            match plist_manager::migrate_single_schedule_plists() {
+                Ok(migrated) => {
+                    for (tool, schedule_id) in migrated {
+                        println!("Moved the {} single-schedule plist to schedule {}", tool, schedule_id);
+                        if let Err(err) = rename_schedule_history(&tool, "legacy", &schedule_id) {
+                            eprintln!("Failed to update the history of schedule {}: {}", schedule_id, err);
+                        }
+                    }
+                }
+                Err(err) => eprintln!("Failed to migrate single-schedule plists: {}", err),
+            }
             let state = app.state::<AppState>().inner().clone();
             tauri::async_runtime::spawn(async move {
                 if let Err(err) = run_missed_schedules(state).await {
                     eprintln!("Failed to run missed schedules: {}", err);
                 }
             });
+            if scheduler::backend().name() == "in-process" {
+                let state = app.state::<AppState>().inner().clone();
+                tauri::async_runtime::spawn(run_in_process_scheduler(state));
+            }
+            // DST switches in either zone shift the local times of zoned schedules
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    if let Err(err) = refresh_zoned_schedules() {
+                        eprintln!("Failed to refresh zoned schedules: {}", err);
+                    }
+                    tokio::time::sleep(std::time::Duration::from_secs(60 * 60)).await;
+                }
+            });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -1039,10 +2217,63 @@ pub fn run() {
             check_iterm_status,
             register_schedule,
             unregister_schedule,
+            pause_schedule,
+            resume_schedule,
             update_schedule,
             get_registered_schedules,
-            get_schedule_history
+            reconcile_schedules,
+            get_next_occurrences,
+            get_schedule_calendar,
+            get_schedule_history,
+            get_blackout_calendars,
+            save_blackout_calendar,
+            delete_blackout_calendar,
+            import_blackout_ics
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval_input(value: Option<u32>, unit: &str) -> ScheduleInput {
+        ScheduleInput {
+            tool: "claude".to_string(),
+            execution_time: vec!["09:00".to_string()],
+            target_directory: "/tmp".to_string(),
+            command_args: "hello".to_string(),
+            title: String::new(),
+            schedule_type: Some("interval".to_string()),
+            interval_value: value,
+            interval_unit: Some(unit.to_string()),
+            active_start: None,
+            active_end: None,
+            start_date: Some("2025-03-10".to_string()),
+            weekdays: None,
+            month_day: None,
+            month_week: None,
+            cron_expression: None,
+            end_date: None,
+            max_runs: None,
+            timezone: None,
+            blackout_calendars: None,
+            jitter_minutes: None,
+            after_tool: None,
+            after_schedule_id: None,
+            after_status: None,
+        }
+    }
+
+    #[test]
+    fn intervals_must_be_at_least_one() {
+        for unit in ["days", "hours", "minutes"] {
+            assert!(build_config(interval_input(Some(0), unit), None).is_err(), "{}", unit);
+            let config = build_config(interval_input(Some(2), unit), None).unwrap();
+            assert_eq!(config.interval_value, Some(2));
+        }
+        assert!(build_config(interval_input(None, "days"), None).is_err());
+        assert!(build_config(interval_input(None, "minutes"), None).is_err());
+    }
+}
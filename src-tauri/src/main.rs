@@ -2,5 +2,22 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // The run scripts call back into the app to decide whether today is a run day
+    if std::env::args().nth(1).as_deref() == Some("check-due") {
+        std::process::exit(tauri_cli_scheduler_lib::check_due());
+    }
+    // ...to read the schedule their job was written for
+    if std::env::args().nth(1).as_deref() == Some("schedule-env") {
+        std::process::exit(tauri_cli_scheduler_lib::schedule_env());
+    }
+    // ...to unregister a schedule that has finished
+    if std::env::args().nth(1).as_deref() == Some("unregister") {
+        std::process::exit(tauri_cli_scheduler_lib::unregister_self());
+    }
+    // ...and to start schedules chained after the one that just ran
+    if std::env::args().nth(1).as_deref() == Some("run-followups") {
+        let status = std::env::args().nth(2).unwrap_or_default();
+        std::process::exit(tauri_cli_scheduler_lib::run_followups(&status));
+    }
     tauri_cli_scheduler_lib::run()
 }
@@ -0,0 +1,668 @@
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Timelike, Utc,
+};
+
+use std::ops::ControlFlow;
+
+use crate::cron::{self, CronSchedule};
+use crate::plist_manager::{self, RegisteredSchedule};
+
+pub fn parse_schedule_time(execution_time: &str) -> Option<NaiveTime> {
+    let parts: Vec<&str> = execution_time.split(':').collect();
+    if parts.len() != 2 {
+        return None;
+    }
+    let hour: u32 = parts[0].parse().ok()?;
+    let minute: u32 = parts[1].parse().ok()?;
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// The schedule's IANA time zone, or `None` to use the system zone
+pub fn parse_timezone(timezone: &Option<String>) -> Option<chrono_tz::Tz> {
+    timezone.as_deref()?.parse::<chrono_tz::Tz>().ok()
+}
+
+/// System-zone (hour, minute) pairs at which `times` in `tz` fall today and tomorrow.
+/// Covering both days keeps a plist valid across a DST switch in either zone.
+pub fn local_fire_times(times: &[NaiveTime], tz: chrono_tz::Tz, now: DateTime<Utc>) -> Vec<(u32, u32)> {
+    let today = now.with_timezone(&tz).date_naive();
+    let mut local_times: Vec<(u32, u32)> = [Some(today), today.succ_opt()]
+        .into_iter()
+        .flatten()
+        .flat_map(|date| times.iter().filter_map(move |time| resolve_local(&tz, date, *time)))
+        .map(|dt| {
+            let local = dt.with_timezone(&Local);
+            (local.hour(), local.minute())
+        })
+        .collect();
+    local_times.sort_unstable();
+    local_times.dedup();
+    local_times
+}
+
+/// System-zone date-times at which `times` in `tz` fall on `date`, for one-off schedules
+/// whose date may be a different day in the system zone
+pub fn local_fire_datetimes(date: NaiveDate, times: &[NaiveTime], tz: chrono_tz::Tz) -> Vec<NaiveDateTime> {
+    fire_datetimes_in(date, times, tz, &Local)
+}
+
+fn fire_datetimes_in<Tz: TimeZone, Target: TimeZone>(
+    date: NaiveDate,
+    times: &[NaiveTime],
+    tz: Tz,
+    target: &Target,
+) -> Vec<NaiveDateTime> {
+    let mut datetimes: Vec<NaiveDateTime> = times
+        .iter()
+        .filter_map(|time| resolve_local(&tz, date, *time))
+        .map(|dt| dt.with_timezone(target).naive_local())
+        .collect();
+    datetimes.sort_unstable();
+    datetimes.dedup();
+    datetimes
+}
+
+/// Resolve a wall-clock time in `tz`. A time repeated when clocks go back resolves to
+/// its first occurrence; a time skipped when clocks go forward moves past the gap.
+fn resolve_local<Tz: TimeZone>(tz: &Tz, date: NaiveDate, time: NaiveTime) -> Option<DateTime<Tz>> {
+    let naive = date.and_time(time);
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) => Some(dt),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        LocalResult::None => tz
+            .from_local_datetime(&(naive + chrono::Duration::hours(1)))
+            .earliest(),
+    }
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first_of_next| first_of_next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(31)
+}
+
+/// Whether a monthly schedule fires on `date`: a fixed day, the last day, or the nth weekday
+fn matches_monthly_date(schedule: &RegisteredSchedule, date: NaiveDate) -> bool {
+    let day = date.day();
+    let last_day = days_in_month(date);
+    match (schedule.month_day, schedule.month_week) {
+        (Some(-1), _) => day == last_day,
+        (Some(month_day), _) => month_day > 0 && day == month_day as u32,
+        (None, Some(month_week)) => {
+            let weekdays = plist_manager::resolve_weekdays(&schedule.weekdays, &None);
+            if !weekdays.contains(&date.weekday().num_days_from_sunday()) {
+                return false;
+            }
+            if month_week == -1 {
+                day + 7 > last_day
+            } else {
+                month_week > 0 && (day - 1) / 7 + 1 == month_week as u32
+            }
+        }
+        (None, None) => false,
+    }
+}
+
+fn parse_date(date_str: &Option<String>) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date_str.as_deref()?, "%Y-%m-%d").ok()
+}
+
+/// Whether the schedule fires on `date` at all. Day counts are calendar days,
+/// so they do not drift when a DST transition makes a day 23 or 25 hours long.
+pub fn occurs_on(schedule: &RegisteredSchedule, date: NaiveDate) -> bool {
+    match schedule.schedule_type.as_str() {
+        "daily" => true,
+        "weekly" => plist_manager::resolve_weekdays(&schedule.weekdays, &schedule.start_date)
+            .contains(&date.weekday().num_days_from_sunday()),
+        "monthly" => matches_monthly_date(schedule, date),
+        "interval" => {
+            let start_date = parse_date(&schedule.start_date);
+            if plist_manager::sub_daily_interval_minutes(&schedule.interval_unit, schedule.interval_value)
+                .is_some()
+            {
+                return start_date.is_none_or(|start| date >= start);
+            }
+            let (Some(start_date), Some(interval)) = (start_date, schedule.interval_value) else {
+                return false;
+            };
+            interval > 0
+                && date >= start_date
+                && (date - start_date).num_days() % interval as i64 == 0
+        }
+        "cron" => schedule
+            .cron_expression
+            .as_deref()
+            .and_then(|expression| CronSchedule::parse(expression).ok())
+            .is_some_and(|cron| cron.matches_date(date)),
+        "once" => parse_date(&schedule.start_date) == Some(date),
+        // Chained schedules may run on any day their upstream finishes
+        "chain" => true,
+        _ => false,
+    }
+}
+
+/// Latest of `times` on `date` that is not after `now` (`times` in ascending order)
+fn latest_on_date<Tz: TimeZone>(
+    date: NaiveDate,
+    times: &[NaiveTime],
+    now: &DateTime<Tz>,
+) -> Option<DateTime<Tz>> {
+    let tz = now.timezone();
+    times
+        .iter()
+        .rev()
+        .filter_map(|time| resolve_local(&tz, date, *time))
+        .find(|candidate| candidate <= now)
+}
+
+fn minutes_of_day(time: NaiveTime) -> i64 {
+    (time.hour() * 60 + time.minute()) as i64
+}
+
+/// Sub-daily slots on `date`, in order. Slots restart at the start of the active
+/// window (or midnight) every day and stop at the window end.
+fn sub_daily_slots_on(
+    schedule: &RegisteredSchedule,
+    interval_minutes: u32,
+    date: NaiveDate,
+) -> Vec<NaiveDateTime> {
+    if interval_minutes == 0 {
+        return Vec::new();
+    }
+    let window_start = schedule
+        .active_start
+        .as_deref()
+        .and_then(parse_schedule_time)
+        .unwrap_or(NaiveTime::MIN);
+    let window_minutes = match schedule.active_end.as_deref().and_then(parse_schedule_time) {
+        Some(window_end) => (minutes_of_day(window_end) - minutes_of_day(window_start)).rem_euclid(24 * 60),
+        None => 24 * 60 - 1,
+    };
+    let window_open = date.and_time(window_start);
+    (0..=window_minutes)
+        .step_by(interval_minutes as usize)
+        .map(|offset| window_open + chrono::Duration::minutes(offset))
+        .collect()
+}
+
+/// Latest sub-daily slot at or before `now`
+fn last_sub_daily_slot<Tz: TimeZone>(
+    schedule: &RegisteredSchedule,
+    interval_minutes: u32,
+    now: &DateTime<Tz>,
+) -> Option<DateTime<Tz>> {
+    let now_naive = now.naive_local();
+    let today = now_naive.date();
+    // A window that wraps midnight may have opened yesterday
+    for date in [today, today.pred_opt()?] {
+        if !occurs_on(schedule, date) {
+            continue;
+        }
+        let slot = sub_daily_slots_on(schedule, interval_minutes, date)
+            .into_iter()
+            .rev()
+            .find(|slot| *slot <= now_naive);
+        if let Some(slot) = slot {
+            return resolve_local(&now.timezone(), slot.date(), slot.time());
+        }
+    }
+    None
+}
+
+/// Wall-clock fire times of a calendar-based schedule, ascending and de-duplicated
+fn schedule_times(schedule: &RegisteredSchedule) -> Vec<NaiveTime> {
+    let mut times: Vec<NaiveTime> = if schedule.schedule_type == "cron" {
+        schedule
+            .cron_expression
+            .as_deref()
+            .and_then(|expression| CronSchedule::parse(expression).ok())
+            .map(|cron| cron.times())
+            .unwrap_or_default()
+    } else {
+        schedule
+            .execution_time
+            .iter()
+            .filter_map(|time| parse_schedule_time(time))
+            .collect()
+    };
+    times.sort();
+    times.dedup();
+    times
+}
+
+/// The most recent time at or before `now` the schedule should have fired, evaluated in
+/// `now`'s time zone
+pub fn get_last_scheduled_time<Tz: TimeZone>(
+    schedule: &RegisteredSchedule,
+    now: &DateTime<Tz>,
+) -> Option<DateTime<Tz>> {
+    if schedule.schedule_type == "interval" {
+        if let Some(minutes) =
+            plist_manager::sub_daily_interval_minutes(&schedule.interval_unit, schedule.interval_value)
+        {
+            return last_sub_daily_slot(schedule, minutes, now);
+        }
+    }
+
+    let times = schedule_times(schedule);
+    if times.is_empty() {
+        return None;
+    }
+
+    // Walk back far enough to reach the previous occurrence of any rule
+    // (cron's lookback also covers monthly rules such as Feb 29 or the 31st)
+    let lookback_days = match schedule.schedule_type.as_str() {
+        "interval" => schedule.interval_value? + 1,
+        _ => cron::MAX_LOOKBACK_DAYS,
+    };
+    let mut date = now.naive_local().date();
+    for _ in 0..lookback_days {
+        if occurs_on(schedule, date) {
+            if let Some(candidate) = latest_on_date(date, &times, now) {
+                return Some(candidate);
+            }
+        }
+        date = date.pred_opt()?;
+    }
+    None
+}
+
+/// Walk the schedule's fire times in order, starting on `first_date` in `tz`, until
+/// `visit` breaks, the end date passes, or a full lookback span (e.g. after a past
+/// 'once' date) goes by without any fire time. Days for which `skip_date` returns
+/// true are left out.
+fn scan_occurrences<Tz: TimeZone>(
+    schedule: &RegisteredSchedule,
+    tz: &Tz,
+    first_date: NaiveDate,
+    skip_date: impl Fn(NaiveDate) -> bool,
+    mut visit: impl FnMut(DateTime<Tz>) -> ControlFlow<()>,
+) {
+    let sub_daily_minutes = if schedule.schedule_type == "interval" {
+        plist_manager::sub_daily_interval_minutes(&schedule.interval_unit, schedule.interval_value)
+    } else {
+        None
+    };
+    let times = schedule_times(schedule);
+    if sub_daily_minutes.is_none() && times.is_empty() {
+        return;
+    }
+    let end_date = parse_date(&schedule.end_date);
+
+    let mut date = first_date;
+    let mut last: Option<DateTime<Tz>> = None;
+    let mut idle_days = 0;
+    while idle_days <= cron::MAX_LOOKBACK_DAYS && end_date.is_none_or(|end| date <= end) {
+        idle_days += 1;
+        if occurs_on(schedule, date) && !skip_date(date) {
+            let slots: Vec<NaiveDateTime> = match sub_daily_minutes {
+                Some(minutes) => sub_daily_slots_on(schedule, minutes, date),
+                None => times.iter().map(|time| date.and_time(*time)).collect(),
+            };
+            for slot in slots {
+                let Some(candidate) = resolve_local(tz, slot.date(), slot.time()) else {
+                    continue;
+                };
+                // A time moved past a DST gap may coincide with the next slot
+                if last.as_ref().is_some_and(|last| candidate <= *last) {
+                    continue;
+                }
+                last = Some(candidate.clone());
+                idle_days = 0;
+                if visit(candidate).is_break() {
+                    return;
+                }
+            }
+        }
+        let Some(next) = date.succ_opt() else {
+            return;
+        };
+        date = next;
+    }
+}
+
+/// The next `count` times after `now` the schedule will fire, evaluated in `now`'s time
+/// zone. Stops at the end date; days for which `skip_date` returns true are left out.
+pub fn get_next_scheduled_times<Tz: TimeZone>(
+    schedule: &RegisteredSchedule,
+    now: &DateTime<Tz>,
+    count: usize,
+    skip_date: impl Fn(NaiveDate) -> bool,
+) -> Vec<DateTime<Tz>> {
+    let mut occurrences = Vec::new();
+    if count == 0 {
+        return occurrences;
+    }
+    // A window that wraps midnight may still have slots left from yesterday
+    let Some(first_date) = now.naive_local().date().pred_opt() else {
+        return occurrences;
+    };
+    scan_occurrences(schedule, &now.timezone(), first_date, skip_date, |candidate| {
+        if candidate > *now {
+            occurrences.push(candidate);
+        }
+        if occurrences.len() >= count {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    occurrences
+}
+
+/// Every time in `from..=to` the schedule fires, evaluated in `from`'s time zone
+pub fn get_scheduled_times_between<Tz: TimeZone>(
+    schedule: &RegisteredSchedule,
+    from: &DateTime<Tz>,
+    to: &DateTime<Tz>,
+) -> Vec<DateTime<Tz>> {
+    let mut occurrences = Vec::new();
+    let Some(first_date) = from.naive_local().date().pred_opt() else {
+        return occurrences;
+    };
+    scan_occurrences(schedule, &from.timezone(), first_date, |_| false, |candidate| {
+        if candidate > *to {
+            return ControlFlow::Break(());
+        }
+        if candidate >= *from {
+            occurrences.push(candidate);
+        }
+        ControlFlow::Continue(())
+    });
+    occurrences
+}
+
+/// `get_last_scheduled_time` evaluated in the schedule's own time zone, falling back to
+/// the system zone
+pub fn last_scheduled_in_zone(
+    schedule: &RegisteredSchedule,
+    now: DateTime<Utc>,
+) -> Option<DateTime<FixedOffset>> {
+    match parse_timezone(&schedule.timezone) {
+        Some(tz) => get_last_scheduled_time(schedule, &now.with_timezone(&tz)).map(|dt| dt.fixed_offset()),
+        None => get_last_scheduled_time(schedule, &now.with_timezone(&Local)).map(|dt| dt.fixed_offset()),
+    }
+}
+
+/// `get_next_scheduled_times` evaluated in the schedule's own time zone, falling back to
+/// the system zone
+pub fn next_scheduled_in_zone(
+    schedule: &RegisteredSchedule,
+    now: DateTime<Utc>,
+    count: usize,
+    skip_date: impl Fn(NaiveDate) -> bool,
+) -> Vec<DateTime<FixedOffset>> {
+    match parse_timezone(&schedule.timezone) {
+        Some(tz) => get_next_scheduled_times(schedule, &now.with_timezone(&tz), count, skip_date)
+            .into_iter()
+            .map(|dt| dt.fixed_offset())
+            .collect(),
+        None => get_next_scheduled_times(schedule, &now.with_timezone(&Local), count, skip_date)
+            .into_iter()
+            .map(|dt| dt.fixed_offset())
+            .collect(),
+    }
+}
+
+/// `get_scheduled_times_between` evaluated in the schedule's own time zone, falling back
+/// to the system zone
+pub fn scheduled_between_in_zone(
+    schedule: &RegisteredSchedule,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<DateTime<FixedOffset>> {
+    match parse_timezone(&schedule.timezone) {
+        Some(tz) => get_scheduled_times_between(schedule, &from.with_timezone(&tz), &to.with_timezone(&tz))
+            .into_iter()
+            .map(|dt| dt.fixed_offset())
+            .collect(),
+        None => get_scheduled_times_between(schedule, &from.with_timezone(&Local), &to.with_timezone(&Local))
+            .into_iter()
+            .map(|dt| dt.fixed_offset())
+            .collect(),
+    }
+}
+
+/// Today's date in the schedule's own time zone, falling back to the system zone
+pub fn today_in_zone(schedule: &RegisteredSchedule, now: DateTime<Utc>) -> NaiveDate {
+    match parse_timezone(&schedule.timezone) {
+        Some(tz) => now.with_timezone(&tz).date_naive(),
+        None => now.with_timezone(&Local).date_naive(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+
+    fn schedule(schedule_type: &str, times: &[&str]) -> RegisteredSchedule {
+        RegisteredSchedule {
+            tool: "claude".to_string(),
+            schedule_id: "test".to_string(),
+            title: "test".to_string(),
+            execution_time: times.iter().map(|time| time.to_string()).collect(),
+            target_directory: String::new(),
+            command_args: String::new(),
+            created_at: String::new(),
+            schedule_type: schedule_type.to_string(),
+            interval_value: None,
+            start_date: None,
+            weekdays: None,
+            month_day: None,
+            month_week: None,
+            cron_expression: None,
+            end_date: None,
+            max_runs: None,
+            interval_unit: None,
+            active_start: None,
+            active_end: None,
+            timezone: None,
+            blackout_calendars: None,
+            jitter_minutes: None,
+            enabled: true,
+            after_tool: None,
+            after_schedule_id: None,
+            after_status: None,
+            updated_at: None,
+            last_modified_by: None,
+        }
+    }
+
+    fn interval_schedule(days: u32, start_date: &str, time: &str) -> RegisteredSchedule {
+        RegisteredSchedule {
+            interval_value: Some(days),
+            start_date: Some(start_date.to_string()),
+            ..schedule("interval", &[time])
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn ny(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<chrono_tz::Tz> {
+        New_York
+            .from_local_datetime(&date(y, m, d).and_hms_opt(h, min, 0).unwrap())
+            .earliest()
+            .unwrap()
+    }
+
+    #[test]
+    fn interval_counts_calendar_days_across_spring_forward() {
+        // 2024-03-10 is only 23 hours long in New York
+        let schedule = interval_schedule(2, "2024-03-09", "09:00");
+        assert!(occurs_on(&schedule, date(2024, 3, 9)));
+        assert!(!occurs_on(&schedule, date(2024, 3, 10)));
+        assert!(occurs_on(&schedule, date(2024, 3, 11)));
+        assert!(occurs_on(&schedule, date(2024, 3, 13)));
+    }
+
+    #[test]
+    fn interval_counts_calendar_days_across_fall_back() {
+        // 2024-11-03 is 25 hours long in New York
+        let schedule = interval_schedule(1, "2024-11-02", "00:30");
+        for day in 2..=5 {
+            assert!(occurs_on(&schedule, date(2024, 11, day)));
+        }
+        let schedule = interval_schedule(3, "2024-11-01", "00:30");
+        assert!(occurs_on(&schedule, date(2024, 11, 4)));
+        assert!(!occurs_on(&schedule, date(2024, 11, 3)));
+        assert!(!occurs_on(&schedule, date(2024, 11, 5)));
+    }
+
+    #[test]
+    fn interval_not_due_before_start_date() {
+        let schedule = interval_schedule(2, "2024-03-09", "09:00");
+        assert!(!occurs_on(&schedule, date(2024, 3, 7)));
+    }
+
+    #[test]
+    fn last_interval_run_after_spring_forward() {
+        let schedule = interval_schedule(2, "2024-03-09", "09:00");
+        let last = get_last_scheduled_time(&schedule, &ny(2024, 3, 12, 8, 0)).unwrap();
+        assert_eq!(last, ny(2024, 3, 11, 9, 0));
+    }
+
+    #[test]
+    fn last_interval_run_after_fall_back() {
+        let schedule = interval_schedule(2, "2024-11-01", "09:00");
+        let last = get_last_scheduled_time(&schedule, &ny(2024, 11, 4, 8, 0)).unwrap();
+        assert_eq!(last, ny(2024, 11, 3, 9, 0));
+    }
+
+    #[test]
+    fn time_in_spring_forward_gap_moves_past_gap() {
+        // 02:30 does not exist on 2024-03-10 in New York
+        let schedule = schedule("daily", &["02:30"]);
+        let last = get_last_scheduled_time(&schedule, &ny(2024, 3, 10, 12, 0)).unwrap();
+        assert_eq!(last, ny(2024, 3, 10, 3, 30));
+    }
+
+    #[test]
+    fn repeated_time_on_fall_back_fires_once() {
+        // 01:30 happens twice on 2024-11-03 in New York; only the first counts
+        let schedule = schedule("daily", &["01:30"]);
+        let first = New_York
+            .from_local_datetime(&date(2024, 11, 3).and_hms_opt(1, 30, 0).unwrap())
+            .earliest()
+            .unwrap();
+        let second = first + chrono::Duration::hours(1);
+        assert_eq!(get_last_scheduled_time(&schedule, &second), Some(first));
+    }
+
+    #[test]
+    fn sub_daily_slots_follow_wall_clock_across_dst() {
+        let schedule = RegisteredSchedule {
+            interval_value: Some(6),
+            interval_unit: Some("hours".to_string()),
+            ..schedule("interval", &[])
+        };
+        // Slots restart at local midnight, so 18:00 stays 18:00 on the short day
+        let last = get_last_scheduled_time(&schedule, &ny(2024, 3, 10, 19, 0)).unwrap();
+        assert_eq!(last, ny(2024, 3, 10, 18, 0));
+    }
+
+    #[test]
+    fn zoned_schedule_evaluates_in_its_own_zone() {
+        let schedule = RegisteredSchedule {
+            timezone: Some("America/New_York".to_string()),
+            weekdays: Some(vec![1]),
+            ..schedule("weekly", &["09:00"])
+        };
+        // Monday 09:00 in New York (EDT) is already Monday 22:00 in Tokyo
+        let now = Utc.with_ymd_and_hms(2024, 6, 3, 13, 30, 0).unwrap();
+        let last = last_scheduled_in_zone(&schedule, now).unwrap();
+        assert_eq!(last, ny(2024, 6, 3, 9, 0).fixed_offset());
+        assert_eq!(today_in_zone(&schedule, now), date(2024, 6, 3));
+    }
+
+    #[test]
+    fn once_times_move_to_the_target_zone_date() {
+        let times = [NaiveTime::from_hms_opt(9, 0, 0).unwrap(), NaiveTime::from_hms_opt(20, 0, 0).unwrap()];
+        let tokyo = "Asia/Tokyo".parse::<chrono_tz::Tz>().unwrap();
+        // 20:00 EDT on June 3rd is already June 4th in Tokyo
+        let local = fire_datetimes_in(date(2024, 6, 3), &times, chrono_tz::America::New_York, &tokyo);
+        assert_eq!(
+            local,
+            vec![date(2024, 6, 3).and_hms_opt(22, 0, 0).unwrap(), date(2024, 6, 4).and_hms_opt(9, 0, 0).unwrap()]
+        );
+    }
+
+    #[test]
+    fn monthly_last_day_and_nth_weekday() {
+        let last_day = RegisteredSchedule {
+            month_day: Some(-1),
+            ..schedule("monthly", &["09:00"])
+        };
+        assert!(occurs_on(&last_day, date(2024, 2, 29)));
+        assert!(!occurs_on(&last_day, date(2024, 2, 28)));
+
+        // Second Tuesday
+        let nth_weekday = RegisteredSchedule {
+            month_week: Some(2),
+            weekdays: Some(vec![2]),
+            ..schedule("monthly", &["09:00"])
+        };
+        assert!(occurs_on(&nth_weekday, date(2024, 3, 12)));
+        assert!(!occurs_on(&nth_weekday, date(2024, 3, 5)));
+    }
+
+    #[test]
+    fn next_interval_runs_skip_off_days_across_dst() {
+        let schedule = interval_schedule(2, "2024-03-09", "09:00");
+        let next = get_next_scheduled_times(&schedule, &ny(2024, 3, 9, 10, 0), 3, |_| false);
+        assert_eq!(next, vec![ny(2024, 3, 11, 9, 0), ny(2024, 3, 13, 9, 0), ny(2024, 3, 15, 9, 0)]);
+    }
+
+    #[test]
+    fn next_runs_stop_at_end_date_and_skip_excluded_days() {
+        let schedule = RegisteredSchedule {
+            end_date: Some("2024-06-05".to_string()),
+            ..schedule("daily", &["09:00"])
+        };
+        let next = get_next_scheduled_times(&schedule, &ny(2024, 6, 3, 9, 0), 10, |day| day == date(2024, 6, 4));
+        assert_eq!(next, vec![ny(2024, 6, 5, 9, 0)]);
+    }
+
+    #[test]
+    fn next_once_run_is_empty_after_its_date() {
+        let schedule = RegisteredSchedule {
+            start_date: Some("2024-06-03".to_string()),
+            ..schedule("once", &["09:00"])
+        };
+        assert_eq!(
+            get_next_scheduled_times(&schedule, &ny(2024, 6, 1, 0, 0), 5, |_| false),
+            vec![ny(2024, 6, 3, 9, 0)]
+        );
+        assert!(get_next_scheduled_times(&schedule, &ny(2024, 6, 3, 9, 0), 5, |_| false).is_empty());
+    }
+
+    #[test]
+    fn next_sub_daily_slots_continue_in_wrapped_window() {
+        let schedule = RegisteredSchedule {
+            interval_value: Some(3),
+            interval_unit: Some("hours".to_string()),
+            active_start: Some("22:00".to_string()),
+            active_end: Some("04:00".to_string()),
+            ..schedule("interval", &[])
+        };
+        // The window opened yesterday at 22:00; 01:00 and 04:00 are still ahead
+        let next = get_next_scheduled_times(&schedule, &ny(2024, 6, 4, 0, 30), 3, |_| false);
+        assert_eq!(next, vec![ny(2024, 6, 4, 1, 0), ny(2024, 6, 4, 4, 0), ny(2024, 6, 4, 22, 0)]);
+    }
+
+    #[test]
+    fn times_between_cover_the_whole_range() {
+        let schedule = schedule("daily", &["09:00", "21:00"]);
+        let times = get_scheduled_times_between(&schedule, &ny(2024, 6, 3, 9, 0), &ny(2024, 6, 4, 9, 0));
+        assert_eq!(times, vec![ny(2024, 6, 3, 9, 0), ny(2024, 6, 3, 21, 0), ny(2024, 6, 4, 9, 0)]);
+    }
+}
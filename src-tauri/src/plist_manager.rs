@@ -1,9 +1,10 @@
-use chrono::Datelike;
-use plist::{Dictionary, Value};
+use crate::scheduler::{drift_entries, job_drift, ScheduleDrift, SchedulerBackend, SCHEDULER_BIN_ENV};
+use chrono::{Datelike, Timelike};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io::Cursor;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,13 +12,29 @@ pub struct LaunchdConfig {
     pub tool: String,                // "claude", "codex", "gemini"
     pub schedule_id: String,
     pub title: String,
-    pub hour: u32,
-    pub minute: u32,
+    pub times: Vec<(u32, u32)>,      // (hour, minute) pairs, fired every matching day
     pub target_directory: String,
     pub command_args: String,
-    pub schedule_type: String,       // "daily", "weekly", "interval"
+    pub schedule_type: String,       // "daily", "weekly", "monthly", "interval", "once", "cron", "chain"
     pub interval_value: Option<u32>,
+    pub interval_unit: Option<String>, // "days" (default), "hours", "minutes"
+    pub active_start: Option<String>, // HH:MM, window for sub-daily intervals
+    pub active_end: Option<String>,  // HH:MM, may be earlier than active_start to wrap midnight
     pub start_date: Option<String>,  // YYYY-MM-DD
+    pub weekdays: Option<Vec<u32>>,  // 0 = Sun, 1 = Mon, ... (weekly, monthly nth weekday)
+    pub month_day: Option<i32>,      // 1-31, or -1 for the last day of the month (monthly)
+    pub month_week: Option<i32>,     // 1-5 = first..fifth, -1 = last occurrence of the weekday (monthly)
+    pub cron_expression: Option<String>, // five-field cron, only for "cron"
+    pub end_date: Option<String>,    // YYYY-MM-DD, last day a recurring schedule may fire
+    pub max_runs: Option<u32>,       // unregister after this many successful runs
+    pub timezone: Option<String>,    // IANA name, e.g. "America/New_York"; None = system zone
+    pub blackout_calendars: Option<Vec<String>>, // IDs of blackout calendars to skip
+    pub jitter_minutes: Option<u32>, // delay each run by a per-day random 0..=N minutes
+    pub enabled: bool,               // false = paused: kept on disk but not loaded into launchd
+    pub after_tool: Option<String>,  // tool of the upstream schedule; None = any (older chains)
+    pub after_schedule_id: Option<String>, // upstream schedule a "chain" schedule follows
+    pub after_status: Option<String>, // "success" (default), "failure" or "any"
+    pub modified_by: Option<String>, // "ui" or "import"; None = not a user change, keeps the last stamp
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,13 +42,234 @@ pub struct RegisteredSchedule {
     pub tool: String,
     pub schedule_id: String,
     pub title: String,
-    pub execution_time: String,
+    pub execution_time: Vec<String>, // "HH:MM" entries in ascending order
     pub target_directory: String,
     pub command_args: String,
     pub created_at: String,
     pub schedule_type: String,
     pub interval_value: Option<u32>,
     pub start_date: Option<String>,
+    #[serde(default)]
+    pub weekdays: Option<Vec<u32>>,
+    #[serde(default)]
+    pub month_day: Option<i32>,
+    #[serde(default)]
+    pub month_week: Option<i32>,
+    #[serde(default)]
+    pub cron_expression: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub max_runs: Option<u32>,
+    #[serde(default)]
+    pub interval_unit: Option<String>,
+    #[serde(default)]
+    pub active_start: Option<String>,
+    #[serde(default)]
+    pub active_end: Option<String>,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub blackout_calendars: Option<Vec<String>>,
+    #[serde(default)]
+    pub jitter_minutes: Option<u32>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub after_tool: Option<String>,
+    #[serde(default)]
+    pub after_schedule_id: Option<String>,
+    #[serde(default)]
+    pub after_status: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>, // RFC 3339, last change by a user or an import
+    #[serde(default)]
+    pub last_modified_by: Option<String>, // "ui" or "import"
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Length of a sub-daily interval in minutes; `None` for day-based intervals
+pub fn sub_daily_interval_minutes(unit: &Option<String>, value: Option<u32>) -> Option<u32> {
+    match unit.as_deref() {
+        Some("hours") => value.map(|v| v.saturating_mul(60)),
+        Some("minutes") => value,
+        _ => None,
+    }
+}
+
+/// One StartCalendarInterval entry. Keys left as `None` are wildcards for launchd.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CalendarInterval {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minute: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hour: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekday: Option<u32>, // 0 = Sun, 1 = Mon, ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub month: Option<u32>,
+}
+
+/// A launchd job as written to its plist; keys follow launchd.plist(5). Everything but
+/// the label is optional when reading, so plists of older versions still load.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LaunchdJob {
+    #[serde(default)]
+    label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_interval: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_calendar"
+    )]
+    start_calendar_interval: Option<Vec<CalendarInterval>>,
+    #[serde(default)]
+    wake_to_run: bool,
+    #[serde(default)]
+    program_arguments: Vec<String>,
+    #[serde(default)]
+    environment_variables: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    standard_out_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    standard_error_path: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    disabled: bool,
+}
+
+/// StartCalendarInterval is a single dict in older plists and an array of dicts since
+fn deserialize_calendar<'de, D>(deserializer: D) -> Result<Option<Vec<CalendarInterval>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        Many(Vec<CalendarInterval>),
+        One(CalendarInterval),
+    }
+    Ok(Option::<OneOrMany>::deserialize(deserializer)?.map(|entries| match entries {
+        OneOrMany::Many(entries) => entries,
+        OneOrMany::One(entry) => vec![entry],
+    }))
+}
+
+impl LaunchdConfig {
+    /// Rebuild the registration config of an already registered schedule
+    pub fn from_registered(schedule: &RegisteredSchedule) -> Result<Self, String> {
+        // Cron and sub-daily schedules derive their times from other fields, and
+        // chained schedules have none
+        let times = if schedule.schedule_type == "cron"
+            || schedule.schedule_type == "chain"
+            || (schedule.schedule_type == "interval"
+                && sub_daily_interval_minutes(&schedule.interval_unit, schedule.interval_value).is_some())
+        {
+            Vec::new()
+        } else {
+            schedule
+                .execution_time
+                .iter()
+                .map(|time| {
+                    crate::occurrence::parse_schedule_time(time)
+                        .map(|time| (time.hour(), time.minute()))
+                        .ok_or_else(|| format!("Invalid execution time: {}", time))
+                })
+                .collect::<Result<Vec<_>, String>>()?
+        };
+        Ok(LaunchdConfig {
+            tool: schedule.tool.clone(),
+            schedule_id: schedule.schedule_id.clone(),
+            title: schedule.title.clone(),
+            times,
+            target_directory: schedule.target_directory.clone(),
+            command_args: schedule.command_args.clone(),
+            schedule_type: schedule.schedule_type.clone(),
+            interval_value: schedule.interval_value,
+            interval_unit: schedule.interval_unit.clone(),
+            active_start: schedule.active_start.clone(),
+            active_end: schedule.active_end.clone(),
+            start_date: schedule.start_date.clone(),
+            weekdays: schedule.weekdays.clone(),
+            month_day: schedule.month_day,
+            month_week: schedule.month_week,
+            cron_expression: schedule.cron_expression.clone(),
+            end_date: schedule.end_date.clone(),
+            max_runs: schedule.max_runs,
+            timezone: schedule.timezone.clone(),
+            blackout_calendars: schedule.blackout_calendars.clone(),
+            jitter_minutes: schedule.jitter_minutes,
+            enabled: schedule.enabled,
+            after_tool: schedule.after_tool.clone(),
+            after_schedule_id: schedule.after_schedule_id.clone(),
+            after_status: schedule.after_status.clone(),
+            modified_by: None,
+        })
+    }
+}
+
+impl LaunchdConfig {
+    /// The schedule this config registers, as `get_registered_schedules` would report it
+    /// as of now. The store keeps the creation time of a schedule it already has.
+    pub fn to_registered(&self) -> RegisteredSchedule {
+        let now = chrono::Local::now().to_rfc3339();
+        RegisteredSchedule {
+            tool: self.tool.clone(),
+            schedule_id: self.schedule_id.clone(),
+            title: self.title.clone(),
+            execution_time: self
+                .times
+                .iter()
+                .map(|(hour, minute)| format!("{:02}:{:02}", hour, minute))
+                .collect(),
+            target_directory: self.target_directory.clone(),
+            command_args: self.command_args.clone(),
+            created_at: now.clone(),
+            schedule_type: self.schedule_type.clone(),
+            interval_value: self.interval_value,
+            start_date: self.start_date.clone(),
+            weekdays: self.weekdays.clone(),
+            month_day: self.month_day,
+            month_week: self.month_week,
+            cron_expression: self.cron_expression.clone(),
+            end_date: self.end_date.clone(),
+            max_runs: self.max_runs,
+            interval_unit: self.interval_unit.clone(),
+            active_start: self.active_start.clone(),
+            active_end: self.active_end.clone(),
+            timezone: self.timezone.clone(),
+            blackout_calendars: self.blackout_calendars.clone(),
+            jitter_minutes: self.jitter_minutes,
+            enabled: self.enabled,
+            after_tool: self.after_tool.clone(),
+            after_schedule_id: self.after_schedule_id.clone(),
+            after_status: self.after_status.clone(),
+            updated_at: self.modified_by.as_ref().map(|_| now),
+            last_modified_by: self.modified_by.clone(),
+        }
+    }
+}
+
+/// Weekdays a weekly schedule fires on (0 = Sun). Schedules saved before explicit weekdays
+/// existed fall back to the weekday of their start date.
+pub fn resolve_weekdays(weekdays: &Option<Vec<u32>>, start_date: &Option<String>) -> Vec<u32> {
+    if let Some(weekdays) = weekdays {
+        if !weekdays.is_empty() {
+            return weekdays.clone();
+        }
+    }
+    start_date
+        .as_ref()
+        .and_then(|date_str| chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok())
+        .map(|date| vec![date.weekday().num_days_from_sunday()])
+        .unwrap_or_default()
 }
 
 pub fn default_tool_options(tool: &str) -> Option<String> {
@@ -43,15 +281,25 @@ pub fn default_tool_options(tool: &str) -> Option<String> {
     }
 }
 
-/// Get the config directory for the scheduler
-fn get_config_dir() -> Result<PathBuf, String> {
+/// Schedules registered as launchd jobs: a plist in the config directory, copied to
+/// ~/Library/LaunchAgents and bootstrapped while the schedule is enabled
+pub struct LaunchdBackend;
+
+/// Get the config directory for the scheduler, relocatable through `ROOT_ENV`
+pub(crate) fn get_config_dir() -> Result<PathBuf, String> {
+    if let Some(root) = crate::scheduler::dir_override(crate::scheduler::ROOT_ENV)? {
+        return Ok(root);
+    }
     dirs::config_dir()
         .ok_or("Could not determine config directory".to_string())
         .map(|p| p.join("tauri-cli-scheduler"))
 }
 
-/// Get the LaunchAgents directory (per-user)
+/// Get the LaunchAgents directory (per-user), relocatable through `AGENTS_DIR_ENV`
 fn get_launch_agents_dir() -> Result<PathBuf, String> {
+    if let Some(dir) = crate::scheduler::dir_override(crate::scheduler::AGENTS_DIR_ENV)? {
+        return Ok(dir);
+    }
     dirs::home_dir()
         .ok_or("Could not determine home directory".to_string())
         .map(|p| p.join("Library/LaunchAgents"))
@@ -95,6 +343,11 @@ fn get_plist_path(tool: &str, schedule_id: &str) -> Result<PathBuf, String> {
     )))
 }
 
+/// launchd label of a schedule's job
+fn job_label(tool: &str, schedule_id: &str) -> String {
+    format!("com.shoma.tauri-cli-scheduler.{}.{}", tool, schedule_id)
+}
+
 /// Get the LaunchAgents plist file path for a specific schedule
 fn get_launch_agents_plist_path(tool: &str, schedule_id: &str) -> Result<PathBuf, String> {
     let launch_agents_dir = get_launch_agents_dir()?;
@@ -150,6 +403,22 @@ fn launchctl_bootstrap(plist_path: &PathBuf) -> Result<(), String> {
     Ok(())
 }
 
+/// Start a loaded job immediately, outside its own trigger
+fn launchctl_kickstart(tool: &str, schedule_id: &str) -> Result<(), String> {
+    let uid = get_user_uid()?;
+    let output = Command::new("launchctl")
+        .arg("kickstart")
+        .arg(format!("gui/{}/{}", uid, job_label(tool, schedule_id)))
+        .output()
+        .map_err(|e| format!("Failed to run launchctl kickstart: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("launchctl kickstart failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
 fn launchctl_bootout(plist_path: &PathBuf) -> Result<(), String> {
     let uid = get_user_uid()?;
     let service_target = format!("gui/{}", uid);
@@ -167,41 +436,233 @@ fn launchctl_bootout(plist_path: &PathBuf) -> Result<(), String> {
     Ok(())
 }
 
+/// Boot out a schedule's job by its label, so it stops even when its plist is gone
+fn launchctl_bootout_label(tool: &str, schedule_id: &str) -> Result<(), String> {
+    let uid = get_user_uid()?;
+    let output = Command::new("launchctl")
+        .arg("bootout")
+        .arg(format!("gui/{}/{}", uid, job_label(tool, schedule_id)))
+        .output()
+        .map_err(|e| format!("Failed to run launchctl bootout: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("launchctl bootout failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+/// Tool and schedule ID of every schedule job loaded in the user's launchd domain
+fn launchctl_loaded_jobs() -> Result<BTreeSet<(String, String)>, String> {
+    let output = Command::new("launchctl")
+        .arg("list")
+        .output()
+        .map_err(|e| format!("Failed to run launchctl list: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("launchctl list failed: {}", stderr.trim()));
+    }
+    // PID, last exit status and label, one job per line
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .filter_map(parse_job_label)
+        .collect())
+}
+
+/// Tool and schedule ID of a schedule's launchd label
+fn parse_job_label(label: &str) -> Option<(String, String)> {
+    let (tool, schedule_id) = label
+        .strip_prefix("com.shoma.tauri-cli-scheduler.")?
+        .split_once('.')?;
+    Some((tool.to_string(), schedule_id.to_string()))
+}
+
+/// Tool and schedule ID of a schedule's plist file name
 fn parse_plist_filename(file_name: &str) -> Option<(String, String)> {
-    let prefix = "com.shoma.tauri-cli-scheduler.";
-    let suffix = ".plist";
-    if !file_name.starts_with(prefix) || !file_name.ends_with(suffix) {
-        return None;
+    parse_job_label(file_name.strip_suffix(".plist")?)
+}
+
+/// Tool and schedule ID of every schedule plist in a directory
+fn schedule_plists_in(dir: &Path) -> Result<BTreeSet<(String, String)>, String> {
+    if !dir.exists() {
+        return Ok(BTreeSet::new());
     }
-    let trimmed = &file_name[prefix.len()..file_name.len() - suffix.len()];
-    let mut parts = trimmed.split('.').collect::<Vec<_>>();
-    if parts.is_empty() {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let mut plists = BTreeSet::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        if let Some(ids) = entry.file_name().to_str().and_then(parse_plist_filename) {
+            plists.insert(ids);
+        }
+    }
+    Ok(plists)
+}
+
+/// Tool of a single-schedule plist, `com.shoma.tauri-cli-scheduler.<tool>.plist`, as the
+/// oldest versions wrote them
+fn parse_single_schedule_plist_filename(file_name: &str) -> Option<String> {
+    let tool = file_name
+        .strip_prefix("com.shoma.tauri-cli-scheduler.")?
+        .strip_suffix(".plist")?;
+    if tool.is_empty() || tool.contains('.') {
         return None;
     }
-    let tool = parts.remove(0).to_string();
-    let schedule_id = if parts.is_empty() {
-        "legacy".to_string()
-    } else {
-        parts.join(".")
-    };
-    Some((tool, schedule_id))
+    Some(tool.to_string())
 }
 
-/// Create a plist for scheduling a command
-pub fn create_plist(config: &LaunchdConfig) -> Result<String, String> {
-    ensure_config_dir()?;
-    let plist_path = get_plist_path(&config.tool, &config.schedule_id)?;
-    let launch_agents_path = get_launch_agents_plist_path(&config.tool, &config.schedule_id)?;
-
-    // Get the path to the run script
-    let script_name = match config.tool.as_str() {
-        "claude" => "run-claude.sh",
-        "codex" => "run-codex.sh",
-        "gemini" => "run-gemini.sh",
-        _ => return Err(format!("Unknown tool: {}", config.tool)),
-    };
+/// StartCalendarInterval entries for every schedule type except sub-daily intervals
+pub(crate) fn calendar_intervals(config: &LaunchdConfig) -> Result<Vec<CalendarInterval>, String> {
+    // launchd only knows the system time zone. Zoned schedules fire daily at the local
+    // equivalents of their times and `check-due` keeps the runs that belong to the schedule.
+    // One-off schedules are translated exactly below.
+    if config.schedule_type != "once" {
+        if let Some(tz) = crate::occurrence::parse_timezone(&config.timezone) {
+            let zone_times: Vec<chrono::NaiveTime> = if config.schedule_type == "cron" {
+                let expression = config
+                    .cron_expression
+                    .as_ref()
+                    .ok_or("cron expression is required for cron schedules".to_string())?;
+                crate::cron::CronSchedule::parse(expression)?.times()
+            } else {
+                config
+                    .times
+                    .iter()
+                    .filter_map(|(hour, minute)| chrono::NaiveTime::from_hms_opt(*hour, *minute, 0))
+                    .collect()
+            };
+            if zone_times.is_empty() {
+                return Err("At least one execution time is required".to_string());
+            }
+            let local_times = crate::occurrence::local_fire_times(&zone_times, tz, chrono::Utc::now());
+            if local_times.len() > crate::cron::MAX_CALENDAR_ENTRIES {
+                return Err(format!(
+                    "schedule expands to {} calendar entries (max {})",
+                    local_times.len(),
+                    crate::cron::MAX_CALENDAR_ENTRIES
+                ));
+            }
+            return Ok(local_times
+                .into_iter()
+                .map(|(hour, minute)| CalendarInterval {
+                    minute: Some(minute),
+                    hour: Some(hour),
+                    ..Default::default()
+                })
+                .collect());
+        }
+    }
+
+    if config.schedule_type == "cron" {
+        let expression = config
+            .cron_expression
+            .as_ref()
+            .ok_or("cron expression is required for cron schedules".to_string())?;
+        return crate::cron::CronSchedule::parse(expression)?.calendar_intervals();
+    }
+
+    // One entry per weekday/day; `None` leaves that key unrestricted
+    let mut weekdays: Vec<Option<u32>> = vec![None];
+    let mut days: Vec<Option<u32>> = vec![None];
+    let mut month = None;
+
+    if config.schedule_type == "weekly" {
+        let resolved = resolve_weekdays(&config.weekdays, &config.start_date);
+        if resolved.is_empty() {
+            return Err("Weekly schedules require at least one weekday".to_string());
+        }
+        weekdays = resolved.into_iter().map(Some).collect();
+    }
+
+    // For 'once', add Day and Month to target a specific date
+    if config.schedule_type == "once" {
+        // A zoned date and time pin one instant, so translate both to the system zone
+        if let Some(tz) = crate::occurrence::parse_timezone(&config.timezone) {
+            let date = config
+                .start_date
+                .as_deref()
+                .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                .ok_or("Once schedules require a start date".to_string())?;
+            let zone_times: Vec<chrono::NaiveTime> = config
+                .times
+                .iter()
+                .filter_map(|(hour, minute)| chrono::NaiveTime::from_hms_opt(*hour, *minute, 0))
+                .collect();
+            if zone_times.is_empty() {
+                return Err("At least one execution time is required".to_string());
+            }
+            return Ok(crate::occurrence::local_fire_datetimes(date, &zone_times, tz)
+                .into_iter()
+                .map(|local| CalendarInterval {
+                    minute: Some(local.minute()),
+                    hour: Some(local.hour()),
+                    day: Some(local.day()),
+                    weekday: None,
+                    month: Some(local.month()),
+                })
+                .collect());
+        }
+        if let Some(date_str) = &config.start_date {
+            if let Ok(date) = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+                days = vec![Some(date.day())];
+                month = Some(date.month());
+            }
+        }
+    }
+
+    // For 'monthly', launchd can only match a fixed Day or Weekday. "Last day" fires on
+    // days 28-31 and "nth weekday" on every matching weekday; the run script keeps the
+    // one occurrence that actually belongs to the schedule.
+    if config.schedule_type == "monthly" {
+        match (config.month_day, config.month_week) {
+            (Some(-1), _) => days = (28..=31).map(Some).collect(),
+            (Some(month_day), _) if (1..=31).contains(&month_day) => {
+                days = vec![Some(month_day as u32)]
+            }
+            (None, Some(_)) => {
+                let resolved = resolve_weekdays(&config.weekdays, &None);
+                if resolved.len() != 1 {
+                    return Err("Monthly weekday schedules require exactly one weekday".to_string());
+                }
+                weekdays = vec![Some(resolved[0])];
+            }
+            _ => return Err("Monthly schedules require a day of month or weekday".to_string()),
+        }
+    }
+
+    // For day-based 'interval', we set it to run daily, but filter execution in the script.
+
+    if config.times.is_empty() {
+        return Err("At least one execution time is required".to_string());
+    }
+    let mut intervals = Vec::with_capacity(weekdays.len() * days.len() * config.times.len());
+    for weekday in &weekdays {
+        for day in &days {
+            for (hour, minute) in &config.times {
+                let entry = CalendarInterval {
+                    minute: Some(*minute),
+                    hour: Some(*hour),
+                    day: *day,
+                    weekday: *weekday,
+                    month,
+                };
+                intervals.push(entry);
+            }
+        }
+    }
+    Ok(intervals)
+}
+
+/// Path of the run script for a tool, in the app bundle or the development directory.
+/// One script runs every tool; it reads the tool from the job's TOOL.
+pub(crate) fn run_script_path(tool: &str) -> Result<PathBuf, String> {
+    if !["claude", "codex", "gemini"].contains(&tool) {
+        return Err(format!("Unknown tool: {}", tool));
+    }
+    let script_name = "run-tool.sh";
 
-    // Find the script in the app bundle or development directory
     let script_path = if let Ok(exe_path) = std::env::current_exe() {
         let app_root = exe_path.parent()
             .and_then(|p| p.parent())
@@ -216,178 +677,225 @@ pub fn create_plist(config: &LaunchdConfig) -> Result<String, String> {
     } else {
         PathBuf::from(format!("./scripts/{}", script_name))
     };
+    Ok(script_path)
+}
 
-    // Build the plist dictionary
-    let mut plist_dict = Dictionary::new();
-
-    // Label
-    plist_dict.insert(
-        "Label".to_string(),
-        Value::String(format!(
-            "com.shoma.tauri-cli-scheduler.{}.{}",
-            config.tool, config.schedule_id
-        )),
-    );
-
-    // StartCalendarInterval
-    let mut cal_interval = Dictionary::new();
-    cal_interval.insert("Hour".to_string(), Value::Integer((config.hour as i64).into()));
-    cal_interval.insert("Minute".to_string(), Value::Integer((config.minute as i64).into()));
+/// Environment a schedule's job is written with. It only names the schedule and where
+/// the app keeps it, so prompts and settings stay out of `launchctl print` and the like;
+/// `backend` is recorded so the scripts and subcommands reach the backend that
+/// registered the job.
+pub(crate) fn job_environment(config: &LaunchdConfig, backend: &str) -> Vec<(String, String)> {
+    let mut env_vars: Vec<(String, String)> = Vec::new();
+    let mut insert = |key: &str, value: String| env_vars.push((key.to_string(), value));
 
-    if config.schedule_type == "weekly" {
-        if let Some(date_str) = &config.start_date {
-            if let Ok(date) = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-                let weekday = date.weekday().num_days_from_sunday(); // 0 = Sun, 1 = Mon, ...
-                cal_interval.insert("Weekday".to_string(), Value::Integer((weekday as i64).into()));
-            }
-        }
+    insert("TOOL", config.tool.clone());
+    insert("SCHEDULE_ID", config.schedule_id.clone());
+    insert(crate::scheduler::BACKEND_ENV, backend.to_string());
+    if let Ok(config_dir) = get_config_dir() {
+        // The run scripts write logs and history here and `check-due` reads it back
+        insert(crate::scheduler::ROOT_ENV, config_dir.to_string_lossy().to_string());
     }
-
-    // For 'once', add Day and Month to target a specific date
-    if config.schedule_type == "once" {
-        if let Some(date_str) = &config.start_date {
-            if let Ok(date) = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-                cal_interval.insert("Day".to_string(), Value::Integer((date.day() as i64).into()));
-                cal_interval.insert("Month".to_string(), Value::Integer((date.month() as i64).into()));
-            }
-        }
+    if let Ok(Some(agents_dir)) = crate::scheduler::dir_override(crate::scheduler::AGENTS_DIR_ENV) {
+        // Used by the run scripts to remove the job files on unregister
+        insert(crate::scheduler::AGENTS_DIR_ENV, agents_dir.to_string_lossy().to_string());
     }
+    if let Ok(exe_path) = std::env::current_exe() {
+        // Used by the run scripts for `schedule-env`, `check-due` and the like
+        insert(SCHEDULER_BIN_ENV, exe_path.to_string_lossy().to_string());
+    }
+    env_vars
+}
 
-    // For 'interval', we set it to run daily, but filter execution in the script.
-    
-    plist_dict.insert("StartCalendarInterval".to_string(), Value::Dictionary(cal_interval));
-    plist_dict.insert("WakeToRun".to_string(), Value::Boolean(true));
-
-    // ProgramArguments
-    let args = vec![
-        Value::String("/bin/bash".to_string()),
-        Value::String(script_path.to_string_lossy().to_string()),
-    ];
-    plist_dict.insert("ProgramArguments".to_string(), Value::Array(args));
-
-    // EnvironmentVariables
-    let mut env_vars = Dictionary::new();
+/// Settings the run scripts read a schedule from, in the order they are printed by the
+/// `schedule-env` subcommand. Jobs written before the schedule store carried them in
+/// their own environment.
+pub(crate) fn schedule_environment(config: &LaunchdConfig) -> Vec<(String, String)> {
+    let mut env_vars: Vec<(String, String)> = Vec::new();
+    let mut insert = |key: &str, value: String| env_vars.push((key.to_string(), value));
 
     match config.tool.as_str() {
-        "claude" => {
-            env_vars.insert("CLAUDE_COMMAND".to_string(), Value::String(config.command_args.clone()));
-        }
-        "codex" => {
-            env_vars.insert("CODEX_COMMAND".to_string(), Value::String(config.command_args.clone()));
-        }
-        "gemini" => {
-            env_vars.insert("GEMINI_COMMAND".to_string(), Value::String(config.command_args.clone()));
-        }
+        "claude" => insert("CLAUDE_COMMAND", config.command_args.clone()),
+        "codex" => insert("CODEX_COMMAND", config.command_args.clone()),
+        "gemini" => insert("GEMINI_COMMAND", config.command_args.clone()),
         _ => {}
     }
     if let Some(options) = default_tool_options(&config.tool) {
         match config.tool.as_str() {
-            "claude" => {
-                env_vars.insert("CLAUDE_OPTIONS".to_string(), Value::String(options));
-            }
-            "codex" => {
-                env_vars.insert("CODEX_OPTIONS".to_string(), Value::String(options));
-            }
-            "gemini" => {
-                env_vars.insert("GEMINI_OPTIONS".to_string(), Value::String(options));
-            }
+            "claude" => insert("CLAUDE_OPTIONS", options),
+            "codex" => insert("CODEX_OPTIONS", options),
+            "gemini" => insert("GEMINI_OPTIONS", options),
             _ => {}
         }
     }
 
-    env_vars.insert("TARGET_DIRECTORY".to_string(), Value::String(config.target_directory.clone()));
-    env_vars.insert("AUTO_RETRY".to_string(), Value::String("false".to_string()));
-    env_vars.insert("TOOL".to_string(), Value::String(config.tool.clone()));
-    env_vars.insert("SCHEDULE_ID".to_string(), Value::String(config.schedule_id.clone()));
-    env_vars.insert("SCHEDULE_TITLE".to_string(), Value::String(config.title.clone()));
-    
-    // Store schedule info in env vars for script logic and retrieval
-    env_vars.insert("SCHEDULE_TYPE".to_string(), Value::String(config.schedule_type.clone()));
+    insert("TARGET_DIRECTORY", config.target_directory.clone());
+    insert("AUTO_RETRY", "false".to_string());
+    insert("SCHEDULE_TITLE", config.title.clone());
+
+    // Schedule rules for the script's own checks
+    let sub_daily_minutes = if config.schedule_type == "interval" {
+        sub_daily_interval_minutes(&config.interval_unit, config.interval_value)
+    } else {
+        None
+    };
+    insert("SCHEDULE_TYPE", config.schedule_type.clone());
     if let Some(val) = config.interval_value {
-        env_vars.insert("SCHEDULE_INTERVAL_DAYS".to_string(), Value::String(val.to_string()));
+        if sub_daily_minutes.is_some() {
+            insert("SCHEDULE_INTERVAL_VALUE", val.to_string());
+        } else {
+            insert("SCHEDULE_INTERVAL_DAYS", val.to_string());
+        }
+    }
+    if sub_daily_minutes.is_some() {
+        if let Some(unit) = &config.interval_unit {
+            insert("SCHEDULE_INTERVAL_UNIT", unit.clone());
+        }
+        if let (Some(start), Some(end)) = (&config.active_start, &config.active_end) {
+            insert("SCHEDULE_ACTIVE_START", start.clone());
+            insert("SCHEDULE_ACTIVE_END", end.clone());
+        }
     }
     if let Some(date) = &config.start_date {
-        env_vars.insert("SCHEDULE_START_DATE".to_string(), Value::String(date.clone()));
+        insert("SCHEDULE_START_DATE", date.clone());
+    }
+    if !config.times.is_empty() {
+        let times = config
+            .times
+            .iter()
+            .map(|(hour, minute)| format!("{:02}:{:02}", hour, minute))
+            .collect::<Vec<_>>()
+            .join(",");
+        insert("SCHEDULE_TIMES", times);
+    }
+    if let Some(weekdays) = &config.weekdays {
+        if !weekdays.is_empty() {
+            let weekdays = weekdays
+                .iter()
+                .map(|weekday| weekday.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            insert("SCHEDULE_WEEKDAYS", weekdays);
+        }
+    }
+    if let Some(month_day) = config.month_day {
+        insert("SCHEDULE_MONTH_DAY", month_day.to_string());
+    }
+    if let Some(month_week) = config.month_week {
+        insert("SCHEDULE_MONTH_WEEK", month_week.to_string());
+    }
+    if let Some(expression) = &config.cron_expression {
+        insert("SCHEDULE_CRON", expression.clone());
+    }
+    if let Some(end_date) = &config.end_date {
+        insert("SCHEDULE_END_DATE", end_date.clone());
+    }
+    if let Some(max_runs) = config.max_runs {
+        insert("SCHEDULE_MAX_RUNS", max_runs.to_string());
+    }
+    if let Some(timezone) = &config.timezone {
+        insert("SCHEDULE_TIMEZONE", timezone.clone());
+    }
+    if let Some(calendars) = &config.blackout_calendars {
+        if !calendars.is_empty() {
+            insert("SCHEDULE_BLACKOUT_CALENDARS", calendars.join(","));
+        }
+    }
+
+    if let Some(after_tool) = &config.after_tool {
+        insert("SCHEDULE_AFTER_TOOL", after_tool.clone());
+    }
+    if let Some(after_schedule_id) = &config.after_schedule_id {
+        insert("SCHEDULE_AFTER_ID", after_schedule_id.clone());
+    }
+    if let Some(after_status) = &config.after_status {
+        insert("SCHEDULE_AFTER_STATUS", after_status.clone());
+    }
+    if let Some(jitter_minutes) = config.jitter_minutes {
+        insert("SCHEDULE_JITTER_MINUTES", jitter_minutes.to_string());
     }
+    env_vars
+}
 
-    plist_dict.insert("EnvironmentVariables".to_string(), Value::Dictionary(env_vars));
+/// `export` lines setting `env` for /bin/sh, as sourced by the run scripts
+pub(crate) fn shell_exports(env: &[(String, String)]) -> String {
+    env.iter()
+        .map(|(key, value)| format!("export {}='{}'\n", key, value.replace('\'', "'\\''")))
+        .collect()
+}
 
-    // StandardOutPath and StandardErrorPath
-    let log_dir = get_config_dir()?.join("logs");
-    let log_file = log_dir.join(format!("{}.log", config.tool));
-    let error_file = log_dir.join(format!("{}.error.log", config.tool));
+/// A job without the parts that depend on where the app is installed
+fn portable_job(job: &LaunchdJob) -> LaunchdJob {
+    let mut job = job.clone();
+    job.program_arguments.clear();
+    job.environment_variables.remove(SCHEDULER_BIN_ENV);
+    job
+}
 
-    plist_dict.insert("StandardOutPath".to_string(), Value::String(log_file.to_string_lossy().to_string()));
-    plist_dict.insert("StandardErrorPath".to_string(), Value::String(error_file.to_string_lossy().to_string()));
-
-    // Build plist XML manually
-    let mut plist_xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
-    plist_xml.push_str("<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n");
-    plist_xml.push_str("<plist version=\"1.0\">\n");
-    plist_xml.push_str("<dict>\n");
-
-    // Helper function to escape XML strings
-    fn escape_xml(s: &str) -> String {
-        s.replace("&", "&amp;")
-            .replace("<", "&lt;")
-            .replace(">", "&gt;")
-            .replace("\"", "&quot;")
-            .replace("'", "&apos;")
-    }
-
-    // Add each key-value pair to the plist
-    for (key, value) in &plist_dict {
-        plist_xml.push_str(&format!("\t<key>{}</key>\n", escape_xml(key)));
-        match value {
-            Value::String(s) => {
-                plist_xml.push_str(&format!("\t<string>{}</string>\n", escape_xml(s)));
-            }
-            Value::Boolean(b) => {
-                if *b {
-                    plist_xml.push_str("\t<true/>\n");
-                } else {
-                    plist_xml.push_str("\t<false/>\n");
-                }
-            }
-            Value::Dictionary(dict) => {
-                plist_xml.push_str("\t<dict>\n");
-                for (k, v) in dict {
-                    plist_xml.push_str(&format!("\t\t<key>{}</key>\n", escape_xml(k)));
-                    if let Value::Integer(num) = v {
-                        plist_xml.push_str(&format!("\t\t<integer>{}</integer>\n", num));
-                    } else if let Value::String(s) = v {
-                         plist_xml.push_str(&format!("\t\t<string>{}</string>\n", escape_xml(s)));
-                    }
-                }
-                plist_xml.push_str("\t</dict>\n");
-            }
-            Value::Array(arr) => {
-                plist_xml.push_str("\t<array>\n");
-                for item in arr {
-                    if let Value::String(s) = item {
-                        plist_xml.push_str(&format!("\t\t<string>{}</string>\n", escape_xml(s)));
-                    }
-                }
-                plist_xml.push_str("\t</array>\n");
-            }
-            _ => {}
+/// The launchd job a schedule is written as
+fn launchd_job(config: &LaunchdConfig) -> Result<LaunchdJob, String> {
+    let script_path = run_script_path(&config.tool)?;
+    let sub_daily_minutes = if config.schedule_type == "interval" {
+        sub_daily_interval_minutes(&config.interval_unit, config.interval_value)
+    } else {
+        None
+    };
+
+    let mut job = LaunchdJob {
+        label: job_label(&config.tool, &config.schedule_id),
+        wake_to_run: true,
+        program_arguments: vec!["/bin/bash".to_string(), script_path.to_string_lossy().to_string()],
+        environment_variables: job_environment(config, LaunchdBackend.name()).into_iter().collect(),
+        disabled: !config.enabled,
+        ..Default::default()
+    };
+
+    if let Some(minutes) = sub_daily_minutes {
+        // Sub-daily intervals use launchd's own timer; the active window is checked in the script
+        if minutes == 0 {
+            return Err("Interval must be at least one minute".to_string());
         }
+        job.start_interval = Some(minutes as u64 * 60);
+    } else if config.schedule_type == "chain" {
+        // Chained schedules have no trigger of their own; the upstream run kickstarts them
+    } else {
+        job.start_calendar_interval = Some(calendar_intervals(config)?);
     }
 
-    plist_xml.push_str("</dict>\n");
-    plist_xml.push_str("</plist>\n");
+    let log_dir = get_config_dir()?.join("logs");
+    let log_file = log_dir.join(format!("{}.log", config.tool));
+    let error_file = log_dir.join(format!("{}.error.log", config.tool));
+    job.standard_out_path = Some(log_file.to_string_lossy().to_string());
+    job.standard_error_path = Some(error_file.to_string_lossy().to_string());
+
+    Ok(job)
+}
 
-    let plist_bytes = plist_xml.into_bytes();
-    fs::write(&plist_path, plist_bytes)
+/// Create a plist for scheduling a command
+fn create_plist(config: &LaunchdConfig) -> Result<String, String> {
+    ensure_config_dir()?;
+    let plist_path = get_plist_path(&config.tool, &config.schedule_id)?;
+    // The new plist replaces the config plist only once its job is loaded, so a failed
+    // update leaves the previous definition in place
+    let new_path = plist_path.with_extension("plist.new");
+    plist::to_file_xml(&new_path, &launchd_job(config)?)
         .map_err(|e| format!("Failed to write plist file: {}", e))?;
 
-    let launch_agents_dir = get_launch_agents_dir()?;
-    fs::create_dir_all(&launch_agents_dir)
-        .map_err(|e| format!("Failed to create LaunchAgents directory: {}", e))?;
-    fs::copy(&plist_path, &launch_agents_path)
-        .map_err(|e| format!("Failed to copy plist to LaunchAgents: {}", e))?;
+    // A paused schedule only keeps its copy in the config directory
+    let loaded = if config.enabled {
+        load_plist(&new_path, &config.tool, &config.schedule_id)
+    } else {
+        LaunchdBackend.unload_job(&config.tool, &config.schedule_id)
+    };
+    if let Err(e) = loaded {
+        let _ = fs::remove_file(&new_path);
+        return Err(e);
+    }
+    fs::rename(&new_path, &plist_path)
+        .map_err(|e| format!("Failed to write plist file: {}", e))?;
 
-    launchctl_bootstrap(&launch_agents_path)?;
+    if !config.enabled {
+        return Ok(format!("Plist saved at: {} (paused)", plist_path.display()));
+    }
 
     Ok(format!(
         "Plist created at: {} and loaded via launchctl",
@@ -395,22 +903,38 @@ pub fn create_plist(config: &LaunchdConfig) -> Result<String, String> {
     ))
 }
 
+/// Copy `plist_path` into LaunchAgents as the schedule's job and bootstrap it. The copy
+/// replaces the previous one only once it is loaded; when it fails to load, the previous
+/// job is bootstrapped again.
+fn load_plist(plist_path: &Path, tool: &str, schedule_id: &str) -> Result<(), String> {
+    let launch_agents_path = get_launch_agents_plist_path(tool, schedule_id)?;
+    let launch_agents_dir = get_launch_agents_dir()?;
+    fs::create_dir_all(&launch_agents_dir)
+        .map_err(|e| format!("Failed to create LaunchAgents directory: {}", e))?;
+    let new_path = launch_agents_path.with_extension("plist.new");
+    fs::copy(plist_path, &new_path)
+        .map_err(|e| format!("Failed to copy plist to LaunchAgents: {}", e))?;
+
+    // Boots out the previous job, which has the same label
+    if let Err(e) = launchctl_bootstrap(&new_path) {
+        let _ = fs::remove_file(&new_path);
+        if launch_agents_path.exists() {
+            let _ = launchctl_bootstrap(&launch_agents_path);
+        }
+        return Err(e);
+    }
+    fs::rename(&new_path, &launch_agents_path)
+        .map_err(|e| format!("Failed to copy plist to LaunchAgents: {}", e))
+}
+
 /// Delete the plist for a specific tool
-pub fn delete_plist(tool: &str, schedule_id: &str) -> Result<String, String> {
-    let (plist_path, launch_agents_path) = if schedule_id == "legacy" {
-        (
-            get_legacy_plist_path(tool)?,
-            get_legacy_launch_agents_plist_path(tool)?,
-        )
-    } else {
-        (
-            get_plist_path(tool, schedule_id)?,
-            get_launch_agents_plist_path(tool, schedule_id)?,
-        )
-    };
+fn delete_plist(tool: &str, schedule_id: &str) -> Result<String, String> {
+    let plist_path = get_plist_path(tool, schedule_id)?;
+    let launch_agents_path = get_launch_agents_plist_path(tool, schedule_id)?;
 
+    // By label, so a job whose LaunchAgents copy is already gone does not keep firing
+    let _ = launchctl_bootout_label(tool, schedule_id);
     if launch_agents_path.exists() {
-        let _ = launchctl_bootout(&launch_agents_path);
         fs::remove_file(&launch_agents_path)
             .map_err(|e| format!("Failed to delete LaunchAgents plist file: {}", e))?;
     }
@@ -424,32 +948,248 @@ pub fn delete_plist(tool: &str, schedule_id: &str) -> Result<String, String> {
     }
 }
 
-/// Get all registered schedules
-pub fn get_registered_schedules() -> Result<Vec<RegisteredSchedule>, String> {
-    let config_dir = get_config_dir()?;
-    let mut schedules = Vec::new();
+impl SchedulerBackend for LaunchdBackend {
+    fn name(&self) -> &'static str {
+        "launchd"
+    }
 
-    if !config_dir.exists() {
-        return Ok(schedules);
+    fn write_job(&self, config: &LaunchdConfig) -> Result<String, String> {
+        create_plist(config)
+    }
+
+    fn remove_job(&self, tool: &str, schedule_id: &str) -> Result<String, String> {
+        delete_plist(tool, schedule_id)
+    }
+
+    /// Every schedule plist in the config directory. Single-schedule plists are left to
+    /// `migrate_single_schedule_plists`.
+    fn legacy_schedules(&self) -> Result<Vec<RegisteredSchedule>, String> {
+        let config_dir = get_config_dir()?;
+        let mut schedules = Vec::new();
+
+        if !config_dir.exists() {
+            return Ok(schedules);
+        }
+
+        let entries = fs::read_dir(&config_dir)
+            .map_err(|e| format!("Failed to read config directory: {}", e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read config entry: {}", e))?;
+            let path = entry.path();
+            if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+                if parse_plist_filename(file_name).is_none() {
+                    continue;
+                }
+                if let Ok(Some(schedule)) = load_plist_from_path(&path) {
+                    schedules.push(schedule);
+                }
+            }
+        }
+
+        Ok(schedules)
+    }
+
+    /// Copy the config plist to LaunchAgents and bootstrap it
+    fn load_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        load_plist(&get_plist_path(tool, schedule_id)?, tool, schedule_id)
+    }
+
+    /// Boot out the job and remove its LaunchAgents copy, keeping the config plist
+    fn unload_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        let launch_agents_path = get_launch_agents_plist_path(tool, schedule_id)?;
+        let _ = launchctl_bootout_label(tool, schedule_id);
+        if launch_agents_path.exists() {
+            fs::remove_file(&launch_agents_path)
+                .map_err(|e| format!("Failed to delete LaunchAgents plist file: {}", e))?;
+        }
+        Ok(())
+    }
+
+    fn start_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        launchctl_kickstart(tool, schedule_id)
+    }
+
+    fn installed_calendar(&self, tool: &str, schedule_id: &str) -> Result<Vec<CalendarInterval>, String> {
+        let plist_path = get_plist_path(tool, schedule_id)?;
+        Ok(plist::from_file::<_, LaunchdJob>(&plist_path)
+            .ok()
+            .and_then(|job| job.start_calendar_interval)
+            .unwrap_or_default())
+    }
+
+    /// Each schedule lives in its config plist, its LaunchAgents copy and its loaded job.
+    /// Drift in those ("missing-config", "config-differs", "missing-agent",
+    /// "agent-differs", "not-loaded", "paused-but-loaded") is repaired by rewriting the
+    /// job from the store. Plists and jobs of schedules the store no longer has
+    /// ("orphan-file", "orphan-job") are booted out and deleted.
+    fn reconcile_schedules(&self, repair: bool) -> Result<Vec<ScheduleDrift>, String> {
+        let loaded = launchctl_loaded_jobs()?;
+        let config_plists = schedule_plists_in(&get_config_dir()?)?;
+        let agent_plists = schedule_plists_in(&get_launch_agents_dir()?)?;
+        let read_job = |path: PathBuf| plist::from_file::<_, LaunchdJob>(path).ok();
+
+        let mut drifts = Vec::new();
+        let mut known = BTreeSet::new();
+        for schedule in self.get_registered_schedules()? {
+            let ids = (schedule.tool.clone(), schedule.schedule_id.clone());
+            let config = LaunchdConfig::from_registered(&schedule)?;
+            let expected = launchd_job(&config)?;
+
+            let mut kinds = Vec::new();
+            let mut push = |kind: &'static str| {
+                if !kinds.contains(&kind) {
+                    kinds.push(kind);
+                }
+            };
+            match read_job(get_plist_path(&ids.0, &ids.1)?) {
+                None if !config_plists.contains(&ids) => push("missing-config"),
+                None => push("config-differs"),
+                Some(job) => job_drift(&job, &expected, portable_job, "config-differs")
+                    .into_iter()
+                    .for_each(&mut push),
+            }
+            if config.enabled {
+                match read_job(get_launch_agents_plist_path(&ids.0, &ids.1)?) {
+                    None if !agent_plists.contains(&ids) => push("missing-agent"),
+                    None => push("agent-differs"),
+                    Some(job) => job_drift(&job, &expected, portable_job, "agent-differs")
+                        .into_iter()
+                        .for_each(&mut push),
+                }
+                if !loaded.contains(&ids) {
+                    push("not-loaded");
+                }
+            } else if agent_plists.contains(&ids) || loaded.contains(&ids) {
+                push("paused-but-loaded");
+            }
+
+            let repaired = (repair && !kinds.is_empty()).then(|| create_plist(&config).map(|_| ()));
+            drifts.extend(drift_entries(&ids, &kinds, repaired));
+            known.insert(ids);
+        }
+
+        let orphans: BTreeSet<_> = config_plists
+            .iter()
+            .chain(&agent_plists)
+            .chain(&loaded)
+            .filter(|ids| !known.contains(*ids))
+            .collect();
+        for ids in orphans {
+            let mut kinds = Vec::new();
+            if config_plists.contains(ids) || agent_plists.contains(ids) {
+                kinds.push("orphan-file");
+            }
+            if loaded.contains(ids) {
+                kinds.push("orphan-job");
+            }
+
+            let repaired = repair.then(|| {
+                if loaded.contains(ids) {
+                    launchctl_bootout_label(&ids.0, &ids.1)?;
+                }
+                for path in [
+                    get_plist_path(&ids.0, &ids.1)?,
+                    get_launch_agents_plist_path(&ids.0, &ids.1)?,
+                ] {
+                    if path.exists() {
+                        fs::remove_file(&path)
+                            .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+                    }
+                }
+                Ok(())
+            });
+            drifts.extend(drift_entries(ids, &kinds, repaired));
+        }
+        Ok(drifts)
     }
+}
 
+/// Turn the single-schedule plists of the oldest versions into regular schedules with an
+/// ID of their own, then boot out and delete the old jobs. Runs at the app's start, after
+/// the schedule store exists; returns the tool and new ID of every converted schedule.
+pub fn migrate_single_schedule_plists() -> Result<Vec<(String, String)>, String> {
+    let config_dir = get_config_dir()?;
+    if !config_dir.exists() {
+        return Ok(Vec::new());
+    }
     let entries = fs::read_dir(&config_dir)
         .map_err(|e| format!("Failed to read config directory: {}", e))?;
 
-    for entry in entries {
+    let started = chrono::Local::now();
+    let mut migrated = Vec::new();
+    for (index, entry) in entries.enumerate() {
         let entry = entry.map_err(|e| format!("Failed to read config entry: {}", e))?;
-        let path = entry.path();
-        if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
-            if parse_plist_filename(file_name).is_none() {
-                continue;
+        let Some(tool) = entry
+            .file_name()
+            .to_str()
+            .and_then(parse_single_schedule_plist_filename)
+        else {
+            continue;
+        };
+        let plist_path = get_legacy_plist_path(&tool)?;
+
+        // Plists converted in one go get IDs a millisecond apart, skipping IDs that are
+        // already taken
+        let mut id_time = started + chrono::Duration::milliseconds(index as i64);
+        let schedule_id = loop {
+            let schedule_id = format!(
+                "{}{:03}",
+                id_time.format("%Y%m%d%H%M%S"),
+                id_time.timestamp_subsec_millis()
+            );
+            if crate::schedule_store::get_schedule(&tool, &schedule_id)?.is_none() {
+                break schedule_id;
             }
-            if let Ok(Some(schedule)) = load_plist_from_path(&path) {
-                schedules.push(schedule);
+            id_time += chrono::Duration::milliseconds(1);
+        };
+
+        // Set the plist aside first, so it cannot be converted a second time once the new
+        // schedule exists, even if deleting it fails below
+        let migrating_path = plist_path.with_extension("plist.migrating");
+        if let Err(err) = fs::rename(&plist_path, &migrating_path) {
+            eprintln!("Failed to migrate the {} single-schedule plist: {}", tool, err);
+            continue;
+        }
+        let converted = plist::from_file::<_, LaunchdJob>(&migrating_path)
+            .map_err(|e| format!("Failed to parse plist: {}", e))
+            .and_then(|job| {
+                schedule_from_job(&job, Some((tool.clone(), schedule_id.clone())))
+                    .ok_or_else(|| "Failed to parse plist: no schedule".to_string())
+            })
+            .and_then(|schedule| LaunchdConfig::from_registered(&schedule))
+            .and_then(|mut config| {
+                config.modified_by = Some("import".to_string());
+                crate::scheduler::backend().create_schedule(&config)
+            });
+        // Keep the old job when the new one could not be registered, so nothing stops firing
+        if let Err(err) = converted {
+            eprintln!("Failed to migrate the {} single-schedule plist: {}", tool, err);
+            if let Err(err) = fs::rename(&migrating_path, &plist_path) {
+                eprintln!("Failed to restore the {} single-schedule plist: {}", tool, err);
             }
+            continue;
         }
-    }
 
-    Ok(schedules)
+        // The schedule is registered now; cleaning up after the old job is best-effort
+        if let Ok(launch_agents_path) = get_legacy_launch_agents_plist_path(&tool) {
+            if launch_agents_path.exists() {
+                let _ = launchctl_bootout(&launch_agents_path);
+                if let Err(err) = fs::remove_file(&launch_agents_path) {
+                    eprintln!("Failed to delete LaunchAgents plist file: {}", err);
+                }
+            }
+        }
+        if let Err(err) = fs::remove_file(&migrating_path) {
+            eprintln!("Failed to delete plist file: {}", err);
+        }
+        // Stores written before this migration existed list the old job as "legacy"
+        if let Err(err) = crate::schedule_store::remove_schedule(&tool, "legacy") {
+            eprintln!("Failed to remove the legacy {} schedule: {}", tool, err);
+        }
+        migrated.push((tool, schedule_id));
+    }
+    Ok(migrated)
 }
 
 /// Load plist content for a path
@@ -460,109 +1200,371 @@ pub fn load_plist_from_path(plist_path: &PathBuf) -> Result<Option<RegisteredSch
 
     let plist_content = fs::read(plist_path)
         .map_err(|e| format!("Failed to read plist file: {}", e))?;
-
-    let plist_value: Value = plist::from_reader(Cursor::new(plist_content.as_slice()))
+    let job: LaunchdJob = plist::from_reader(Cursor::new(plist_content.as_slice()))
         .map_err(|e| format!("Failed to parse plist: {}", e))?;
 
-    if let Some(dict) = plist_value.as_dictionary() {
-        if let Some(cal_interval) = dict.get("StartCalendarInterval") {
-            if let Some(cal_dict) = cal_interval.as_dictionary() {
-                if let (Some(hour_val), Some(minute_val)) =
-                    (cal_dict.get("Hour"), cal_dict.get("Minute")) {
-                    if let (Some(hour), Some(minute)) =
-                        (hour_val.as_signed_integer(), minute_val.as_signed_integer()) {
-                        
-                        let execution_time = format!("{:02}:{:02}", hour, minute);
-                        
-                        // Parse EnvironmentVariables for extra schedule info
-                        let mut schedule_type = "daily".to_string();
-                        let mut interval_value = None;
-                        let mut start_date = None;
-                        let mut schedule_id = None;
-                        let mut tool = None;
-                        let mut title = None;
-                        let mut command_args = None;
-                        let mut target_directory = None;
-
-                        if let Some(env_vars) = dict.get("EnvironmentVariables") {
-                            if let Some(env_dict) = env_vars.as_dictionary() {
-                                if let Some(Value::String(s)) = env_dict.get("SCHEDULE_TYPE") {
-                                    schedule_type = s.clone();
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("SCHEDULE_INTERVAL_DAYS") {
-                                    if let Ok(v) = s.parse::<u32>() {
-                                        interval_value = Some(v);
-                                    }
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("SCHEDULE_START_DATE") {
-                                    start_date = Some(s.clone());
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("SCHEDULE_ID") {
-                                    schedule_id = Some(s.clone());
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("SCHEDULE_TITLE") {
-                                    title = Some(s.clone());
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("TOOL") {
-                                    tool = Some(s.clone());
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("CLAUDE_COMMAND") {
-                                    command_args = Some(s.clone());
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("CODEX_COMMAND") {
-                                    command_args = Some(s.clone());
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("GEMINI_COMMAND") {
-                                    command_args = Some(s.clone());
-                                }
-                                if let Some(Value::String(s)) = env_dict.get("TARGET_DIRECTORY") {
-                                    target_directory = Some(s.clone());
-                                }
-                            }
-                        }
-
-                        let (tool, schedule_id) = if let (Some(t), Some(id)) =
-                            (tool.as_ref(), schedule_id.as_ref())
-                        {
-                            (t.clone(), id.clone())
-                        } else {
-                            let file_name = plist_path
-                                .file_name()
-                                .and_then(|s| s.to_str())
-                                .unwrap_or_default();
-                            if let Some((parsed_tool, parsed_id)) =
-                                parse_plist_filename(file_name)
-                            {
-                                (parsed_tool, parsed_id)
-                            } else {
-                                (
-                                    tool.unwrap_or_else(|| "unknown".to_string()),
-                                    schedule_id.unwrap_or_else(|| "legacy".to_string()),
-                                )
-                            }
-                        };
-
-                        let title = title.unwrap_or_else(|| "無題のスケジュール".to_string());
-                        let command_args = command_args.unwrap_or_default();
-                        let target_directory = target_directory.unwrap_or_default();
-
-                        return Ok(Some(RegisteredSchedule {
-                            tool,
-                            schedule_id,
-                            title,
-                            execution_time,
-                            target_directory,
-                            command_args,
-                            created_at: chrono::Local::now().to_rfc3339(),
-                            schedule_type,
-                            interval_value,
-                            start_date,
-                        }));
-                    }
-                }
+    let fallback_ids = plist_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .and_then(parse_plist_filename);
+    Ok(schedule_from_job(&job, fallback_ids))
+}
+
+/// Rebuild a schedule from its launchd job. EnvironmentVariables carry the schedule
+/// itself; plists written before a variable existed fall back to the job's own triggers.
+fn schedule_from_job(job: &LaunchdJob, fallback_ids: Option<(String, String)>) -> Option<RegisteredSchedule> {
+    let mut env: HashMap<String, String> = job.environment_variables.clone().into_iter().collect();
+    let calendar = job.start_calendar_interval.as_deref().unwrap_or_default();
+
+    let mut times: Vec<String> = calendar
+        .iter()
+        .filter_map(|entry| Some(format!("{:02}:{:02}", entry.hour?, entry.minute?)))
+        .collect();
+    times.sort();
+    times.dedup();
+    if !times.is_empty() {
+        env.entry("SCHEDULE_TIMES".to_string()).or_insert_with(|| times.join(","));
+    }
+
+    match env.get("SCHEDULE_TYPE").map(String::as_str) {
+        Some("weekly") => {
+            let mut weekdays: Vec<u32> = calendar.iter().filter_map(|entry| entry.weekday).map(|w| w % 7).collect();
+            weekdays.sort_unstable();
+            weekdays.dedup();
+            if !weekdays.is_empty() {
+                let weekdays = weekdays.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(",");
+                env.entry("SCHEDULE_WEEKDAYS".to_string()).or_insert(weekdays);
             }
         }
+        Some("once") => {
+            // The plist only has Month and Day; a one-time run is never in the past
+            let today = chrono::Local::now().date_naive();
+            let date = calendar.first().and_then(|entry| {
+                let (month, day) = (entry.month?, entry.day?);
+                chrono::NaiveDate::from_ymd_opt(today.year(), month, day)
+                    .filter(|date| *date >= today)
+                    .or_else(|| chrono::NaiveDate::from_ymd_opt(today.year() + 1, month, day))
+            });
+            if let Some(date) = date {
+                env.entry("SCHEDULE_START_DATE".to_string())
+                    .or_insert_with(|| date.format("%Y-%m-%d").to_string());
+            }
+        }
+        _ => {}
     }
 
-    Ok(None)
+    if let Some(seconds) = job.start_interval {
+        if !env.contains_key("SCHEDULE_INTERVAL_VALUE") {
+            env.insert("SCHEDULE_TYPE".to_string(), "interval".to_string());
+            env.insert("SCHEDULE_INTERVAL_VALUE".to_string(), (seconds / 60).to_string());
+            env.insert("SCHEDULE_INTERVAL_UNIT".to_string(), "minutes".to_string());
+        }
+    }
+
+    schedule_from_environment(&env, fallback_ids, !job.disabled)
+}
+
+/// Rebuild a schedule from the environment its job was written with (see
+/// `schedule_environment`). `fallback_ids` are read from the job's file name for jobs
+/// written before TOOL and SCHEDULE_ID were set.
+pub(crate) fn schedule_from_environment(
+    env: &HashMap<String, String>,
+    fallback_ids: Option<(String, String)>,
+    enabled: bool,
+) -> Option<RegisteredSchedule> {
+    let var = |key: &str| env.get(key).cloned();
+    let list = |key: &str| {
+        var(key).map(|s| {
+            s.split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let schedule_type = var("SCHEDULE_TYPE").unwrap_or_else(|| "daily".to_string());
+    let interval_value = var("SCHEDULE_INTERVAL_VALUE")
+        .and_then(|s| s.parse::<u32>().ok())
+        .or_else(|| var("SCHEDULE_INTERVAL_DAYS").and_then(|s| s.parse::<u32>().ok()));
+    let interval_unit = var("SCHEDULE_INTERVAL_UNIT");
+    let cron_expression = var("SCHEDULE_CRON");
+    let weekdays = list("SCHEDULE_WEEKDAYS")
+        .map(|weekdays| weekdays.iter().filter_map(|w| w.parse::<u32>().ok()).collect::<Vec<_>>());
+    let command_args = var("CLAUDE_COMMAND")
+        .or_else(|| var("CODEX_COMMAND"))
+        .or_else(|| var("GEMINI_COMMAND"));
+
+    let execution_time = if schedule_type == "cron" {
+        // Cron schedules may have wildcard hours/minutes, so expand the expression instead
+        let cron = crate::cron::CronSchedule::parse(cron_expression.as_ref()?).ok()?;
+        cron.times()
+            .iter()
+            .map(|time| time.format("%H:%M").to_string())
+            .collect()
+    } else {
+        list("SCHEDULE_TIMES").unwrap_or_default()
+    };
+    // Sub-daily intervals fire on the scheduler's timer and chained schedules after their
+    // upstream, so neither has times of day
+    let sub_daily = schedule_type == "interval"
+        && sub_daily_interval_minutes(&interval_unit, interval_value).is_some();
+    if execution_time.is_empty() && !sub_daily && schedule_type != "chain" {
+        return None;
+    }
+
+    // A job that names neither is not one of ours to report
+    let (tool, schedule_id) = match (var("TOOL"), var("SCHEDULE_ID")) {
+        (Some(tool), Some(schedule_id)) => (tool, schedule_id),
+        _ => fallback_ids?,
+    };
+    let created_at =
+        created_at_from_schedule_id(&schedule_id).unwrap_or_else(|| chrono::Local::now().to_rfc3339());
+
+    Some(RegisteredSchedule {
+        tool,
+        schedule_id,
+        title: var("SCHEDULE_TITLE").unwrap_or_else(|| "無題のスケジュール".to_string()),
+        execution_time,
+        target_directory: var("TARGET_DIRECTORY").unwrap_or_default(),
+        command_args: command_args.unwrap_or_default(),
+        created_at,
+        schedule_type,
+        interval_value,
+        start_date: var("SCHEDULE_START_DATE"),
+        weekdays,
+        month_day: var("SCHEDULE_MONTH_DAY").and_then(|s| s.parse::<i32>().ok()),
+        month_week: var("SCHEDULE_MONTH_WEEK").and_then(|s| s.parse::<i32>().ok()),
+        cron_expression,
+        end_date: var("SCHEDULE_END_DATE"),
+        max_runs: var("SCHEDULE_MAX_RUNS").and_then(|s| s.parse::<u32>().ok()),
+        interval_unit,
+        active_start: var("SCHEDULE_ACTIVE_START"),
+        active_end: var("SCHEDULE_ACTIVE_END"),
+        timezone: var("SCHEDULE_TIMEZONE"),
+        blackout_calendars: list("SCHEDULE_BLACKOUT_CALENDARS"),
+        jitter_minutes: var("SCHEDULE_JITTER_MINUTES").and_then(|s| s.parse::<u32>().ok()),
+        enabled,
+        after_tool: var("SCHEDULE_AFTER_TOOL"),
+        after_schedule_id: var("SCHEDULE_AFTER_ID"),
+        after_status: var("SCHEDULE_AFTER_STATUS"),
+        updated_at: None,
+        last_modified_by: None,
+    })
+}
+
+/// Creation time encoded in a schedule ID, which is the local registration time as
+/// YYYYMMDDhhmmss plus milliseconds
+fn created_at_from_schedule_id(schedule_id: &str) -> Option<String> {
+    let seconds = schedule_id.get(..14)?;
+    let millis: u32 = schedule_id.get(14..)?.parse().ok()?;
+    let time = chrono::NaiveDateTime::parse_from_str(seconds, "%Y%m%d%H%M%S").ok()?
+        + chrono::Duration::milliseconds(millis as i64);
+    Some(time.and_local_timezone(chrono::Local).earliest()?.to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(schedule_type: &str) -> LaunchdConfig {
+        LaunchdConfig {
+            tool: "claude".to_string(),
+            schedule_id: "20250101000000000".to_string(),
+            title: "テスト \"quoted\" & <tagged>".to_string(),
+            times: vec![(9, 0), (18, 30)],
+            target_directory: "/tmp/project dir".to_string(),
+            command_args: "review\nthe diff".to_string(),
+            schedule_type: schedule_type.to_string(),
+            interval_value: None,
+            interval_unit: None,
+            active_start: None,
+            active_end: None,
+            start_date: Some("2025-03-10".to_string()),
+            weekdays: None,
+            month_day: None,
+            month_week: None,
+            cron_expression: None,
+            end_date: Some("2025-12-31".to_string()),
+            max_runs: Some(3),
+            timezone: None,
+            blackout_calendars: Some(vec!["holidays".to_string()]),
+            jitter_minutes: Some(10),
+            enabled: true,
+            after_tool: None,
+            after_schedule_id: None,
+            after_status: None,
+            modified_by: None,
+        }
+    }
+
+    fn round_trip(job: &LaunchdJob) -> LaunchdJob {
+        let mut xml = Vec::new();
+        plist::to_writer_xml(&mut xml, job).unwrap();
+        plist::from_reader(Cursor::new(xml.as_slice())).unwrap()
+    }
+
+    fn without_created_at(schedule: RegisteredSchedule) -> serde_json::Value {
+        let mut value = serde_json::to_value(schedule).unwrap();
+        value.as_object_mut().unwrap().remove("created_at");
+        value
+    }
+
+    #[test]
+    fn every_job_key_round_trips() {
+        let job = LaunchdJob {
+            label: job_label("codex", "1"),
+            start_interval: Some(900),
+            start_calendar_interval: Some(vec![
+                CalendarInterval { minute: Some(5), hour: Some(4), day: Some(31), weekday: Some(0), month: Some(12) },
+                CalendarInterval { weekday: Some(7), ..Default::default() },
+            ]),
+            wake_to_run: true,
+            program_arguments: vec!["/bin/bash".to_string(), "/path/run codex.sh".to_string()],
+            environment_variables: [("A", "1"), ("B", "x'y\"z\n")]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            standard_out_path: Some("/tmp/out.log".to_string()),
+            standard_error_path: Some("/tmp/err.log".to_string()),
+            disabled: true,
+        };
+        assert_eq!(round_trip(&job), job);
+    }
+
+    #[test]
+    fn schedules_round_trip_through_their_plist() {
+        let mut weekly = config("weekly");
+        weekly.weekdays = Some(vec![1, 3, 5]);
+        let mut monthly = config("monthly");
+        monthly.month_week = Some(-1);
+        monthly.weekdays = Some(vec![5]);
+        let mut interval = config("interval");
+        interval.times = Vec::new();
+        interval.interval_value = Some(90);
+        interval.interval_unit = Some("minutes".to_string());
+        interval.active_start = Some("22:00".to_string());
+        interval.active_end = Some("06:00".to_string());
+        let mut cron = config("cron");
+        cron.times = vec![(0, 15), (12, 15)];
+        cron.cron_expression = Some("15 0,12 1-7 * 1".to_string());
+        let mut chain = config("chain");
+        chain.times = Vec::new();
+        chain.after_tool = Some("codex".to_string());
+        chain.after_schedule_id = Some("20240101000000000".to_string());
+        chain.after_status = Some("any".to_string());
+        let mut paused = config("daily");
+        paused.enabled = false;
+        paused.timezone = Some("America/New_York".to_string());
+
+        // Job environments carry the config directory, which the scheduler tests relocate
+        let _env = crate::scheduler::tests::ScopedEnv::set(&[]);
+        for config in [config("once"), config("daily"), weekly, monthly, interval, cron, chain, paused] {
+            let job = launchd_job(&config).unwrap();
+            assert_eq!(round_trip(&job), job, "{}", config.schedule_type);
+            // Only jobs written before the schedule store carry the schedule itself
+            assert!(!job.environment_variables.contains_key("CLAUDE_COMMAND"));
+            let mut legacy_job = job.clone();
+            legacy_job.environment_variables.extend(schedule_environment(&config));
+            let schedule = schedule_from_job(&round_trip(&legacy_job), None).unwrap();
+            assert_eq!(
+                without_created_at(schedule),
+                without_created_at(config.to_registered()),
+                "{}",
+                config.schedule_type
+            );
+        }
+    }
+
+    #[test]
+    fn older_plists_fall_back_to_their_triggers() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.shoma.tauri-cli-scheduler.gemini</string>
+    <key>StartCalendarInterval</key>
+    <dict>
+        <key>Hour</key>
+        <integer>7</integer>
+        <key>Minute</key>
+        <integer>45</integer>
+        <key>Weekday</key>
+        <integer>7</integer>
+    </dict>
+    <key>EnvironmentVariables</key>
+    <dict>
+        <key>GEMINI_COMMAND</key>
+        <string>summarize</string>
+        <key>SCHEDULE_TYPE</key>
+        <string>weekly</string>
+    </dict>
+</dict>
+</plist>"#;
+        let job: LaunchdJob = plist::from_reader(Cursor::new(xml.as_bytes())).unwrap();
+        assert_eq!(
+            job.start_calendar_interval,
+            Some(vec![CalendarInterval { minute: Some(45), hour: Some(7), weekday: Some(7), ..Default::default() }])
+        );
+        let tool = parse_single_schedule_plist_filename("com.shoma.tauri-cli-scheduler.gemini.plist").unwrap();
+        let schedule = schedule_from_job(&job, Some((tool, "20250310091530250".to_string()))).unwrap();
+        assert_eq!(schedule.tool, "gemini");
+        assert_eq!(schedule.execution_time, vec!["07:45"]);
+        assert_eq!(schedule.weekdays, Some(vec![0]));
+        assert_eq!(schedule.command_args, "summarize");
+        assert!(schedule.enabled);
+    }
+
+    #[test]
+    fn single_schedule_plists_are_told_apart_by_name() {
+        let name = "com.shoma.tauri-cli-scheduler.codex.20250310091530250.plist";
+        assert_eq!(
+            parse_plist_filename(name),
+            Some(("codex".to_string(), "20250310091530250".to_string()))
+        );
+        assert_eq!(parse_single_schedule_plist_filename(name), None);
+        assert_eq!(parse_plist_filename("com.shoma.tauri-cli-scheduler.codex.plist"), None);
+        assert_eq!(
+            parse_single_schedule_plist_filename("com.shoma.tauri-cli-scheduler.codex.plist"),
+            Some("codex".to_string())
+        );
+    }
+
+    #[test]
+    fn creation_time_comes_from_the_schedule_id() {
+        let created_at = created_at_from_schedule_id("20250310091530250").unwrap();
+        let parsed = chrono::DateTime::parse_from_rfc3339(&created_at).unwrap();
+        assert_eq!(
+            parsed.with_timezone(&chrono::Local).naive_local().to_string(),
+            "2025-03-10 09:15:30.250"
+        );
+        assert_eq!(created_at_from_schedule_id("legacy"), None);
+    }
+
+    #[test]
+    fn failed_load_rolls_back_to_the_previous_job() {
+        use crate::scheduler::tests::{config, FakeScheduler};
+
+        let fake = FakeScheduler::new(
+            "launchd",
+            "launchd",
+            &[("launchctl", r#"[ "$1" = "bootstrap" ] && fail_once && exit 1; exit 0"#)],
+        );
+        let backend = LaunchdBackend;
+        let first = config("1", "first");
+        backend.create_schedule(&first).unwrap();
+
+        let mut moved = config("1", "moved");
+        moved.times = vec![(10, 30)];
+        fake.fail_next();
+        assert!(backend.create_schedule(&moved).is_err());
+        assert_eq!(
+            backend.installed_calendar("claude", "1").unwrap(),
+            calendar_intervals(&first).unwrap()
+        );
+        // The LaunchAgents copy is only replaced once the new job loads
+        let agent = plist::from_file::<_, LaunchdJob>(get_launch_agents_plist_path("claude", "1").unwrap()).unwrap();
+        assert_eq!(agent.start_calendar_interval, Some(calendar_intervals(&first).unwrap()));
+        assert_eq!(backend.get_registered_schedule("claude", "1").unwrap().unwrap().title, "first");
+    }
 }
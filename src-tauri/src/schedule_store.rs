@@ -0,0 +1,322 @@
+use crate::plist_manager::{self, LaunchdConfig, RegisteredSchedule};
+use crate::scheduler::SchedulerBackend;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The registered schedules, kept in the config directory. Jobs are generated from this
+/// file and only carry a schedule's tool and ID; the run scripts read the rest back
+/// through the `schedule-env` subcommand.
+const STORE_FILE: &str = "schedules.json";
+
+/// Bumped whenever the file layout changes in a way older versions cannot read
+const STORE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ScheduleStore {
+    version: u32,
+    schedules: Vec<RegisteredSchedule>,
+}
+
+fn get_store_path() -> Result<PathBuf, String> {
+    Ok(plist_manager::get_config_dir()?.join(STORE_FILE))
+}
+
+/// Store path for writing, creating the config directory if needed
+fn ensure_store_path() -> Result<PathBuf, String> {
+    Ok(plist_manager::ensure_config_dir()?.join(STORE_FILE))
+}
+
+/// Take the advisory lock on a sidecar of the store, so concurrent writers (the app and
+/// the run scripts' subcommands) cannot interleave their load and save. Released when the
+/// returned file is dropped.
+fn lock_store(path: &Path) -> Result<fs::File, String> {
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_extension("json.lock"))
+        .map_err(|e| format!("Failed to open schedule store lock: {}", e))?;
+    lock.lock()
+        .map_err(|e| format!("Failed to lock schedule store: {}", e))?;
+    Ok(lock)
+}
+
+/// All schedules in the store. Before `migrate` ran they are read from the jobs instead.
+pub fn load_schedules() -> Result<Vec<RegisteredSchedule>, String> {
+    load_schedules_at(&get_store_path()?, crate::scheduler::backend())
+}
+
+fn load_schedules_at(path: &Path, backend: &dyn SchedulerBackend) -> Result<Vec<RegisteredSchedule>, String> {
+    if !path.exists() {
+        return backend.legacy_schedules();
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read schedule store: {}", e))?;
+    let store: ScheduleStore = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse schedule store: {}", e))?;
+    if store.version > STORE_VERSION {
+        return Err(format!(
+            "Schedule store version {} is newer than this app supports ({})",
+            store.version, STORE_VERSION
+        ));
+    }
+    Ok(store.schedules)
+}
+
+fn save_schedules(path: &Path, schedules: Vec<RegisteredSchedule>) -> Result<(), String> {
+    let store = ScheduleStore {
+        version: STORE_VERSION,
+        schedules,
+    };
+    let content = serde_json::to_string_pretty(&store)
+        .map_err(|e| format!("Failed to serialize schedule store: {}", e))?;
+    // Write a sibling file and rename it, so a crash never leaves a truncated store
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, content)
+        .map_err(|e| format!("Failed to write schedule store: {}", e))?;
+    fs::rename(&temp_path, path)
+        .map_err(|e| format!("Failed to write schedule store: {}", e))
+}
+
+/// Load a single schedule by tool and ID
+pub fn get_schedule(tool: &str, schedule_id: &str) -> Result<Option<RegisteredSchedule>, String> {
+    Ok(load_schedules()?
+        .into_iter()
+        .find(|schedule| schedule.tool == tool && schedule.schedule_id == schedule_id))
+}
+
+/// Add a schedule or replace the one with the same tool and ID, keeping its creation time.
+/// A schedule saved without a `last_modified_by` is an automatic rewrite and keeps the
+/// modification stamp it had.
+pub fn save_schedule(schedule: RegisteredSchedule) -> Result<(), String> {
+    save_schedule_at(&ensure_store_path()?, crate::scheduler::backend(), schedule)
+}
+
+fn save_schedule_at(
+    path: &Path,
+    backend: &dyn SchedulerBackend,
+    mut schedule: RegisteredSchedule,
+) -> Result<(), String> {
+    let _lock = lock_store(path)?;
+    let mut schedules = load_schedules_at(path, backend)?;
+    match schedules
+        .iter_mut()
+        .find(|existing| existing.tool == schedule.tool && existing.schedule_id == schedule.schedule_id)
+    {
+        Some(existing) => {
+            schedule.created_at = existing.created_at.clone();
+            if schedule.last_modified_by.is_none() {
+                schedule.updated_at = existing.updated_at.clone();
+                schedule.last_modified_by = existing.last_modified_by.clone();
+            }
+            *existing = schedule;
+        }
+        None => schedules.push(schedule),
+    }
+    save_schedules(path, schedules)
+}
+
+/// Remove a schedule. Returns whether the store had it; before `migrate` ran there is
+/// nothing to remove.
+pub fn remove_schedule(tool: &str, schedule_id: &str) -> Result<bool, String> {
+    remove_schedule_at(&get_store_path()?, crate::scheduler::backend(), tool, schedule_id)
+}
+
+fn remove_schedule_at(
+    path: &Path,
+    backend: &dyn SchedulerBackend,
+    tool: &str,
+    schedule_id: &str,
+) -> Result<bool, String> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    let _lock = lock_store(path)?;
+    let mut schedules = load_schedules_at(path, backend)?;
+    let count = schedules.len();
+    schedules.retain(|schedule| !(schedule.tool == tool && schedule.schedule_id == schedule_id));
+    if schedules.len() == count {
+        return Ok(false);
+    }
+    save_schedules(path, schedules)?;
+    Ok(true)
+}
+
+/// Create the store from the schedules that so far only lived in their jobs' environment,
+/// then rewrite those jobs without it. Runs once, at the app's start; returns the number
+/// of schedules migrated.
+pub fn migrate() -> Result<usize, String> {
+    migrate_at(&ensure_store_path()?, crate::scheduler::backend())
+}
+
+fn migrate_at(path: &Path, backend: &dyn SchedulerBackend) -> Result<usize, String> {
+    let _lock = lock_store(path)?;
+    if path.exists() {
+        return Ok(0);
+    }
+    let now = chrono::Local::now().to_rfc3339();
+    let schedules: Vec<RegisteredSchedule> = backend
+        .legacy_schedules()?
+        .into_iter()
+        .map(|schedule| RegisteredSchedule {
+            updated_at: Some(now.clone()),
+            last_modified_by: Some("import".to_string()),
+            ..schedule
+        })
+        .collect();
+    save_schedules(path, schedules.clone())?;
+
+    for schedule in &schedules {
+        if let Err(err) = LaunchdConfig::from_registered(schedule).and_then(|config| backend.write_job(&config)) {
+            eprintln!("Failed to rewrite the job of schedule {}: {}", schedule.schedule_id, err);
+        }
+    }
+    Ok(schedules.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plist_manager::CalendarInterval;
+    use std::sync::Mutex;
+
+    /// Holds the schedules of jobs written before the store existed
+    #[derive(Default)]
+    struct LegacyBackend {
+        legacy: Vec<RegisteredSchedule>,
+        rewritten: Mutex<Vec<String>>,
+    }
+
+    impl SchedulerBackend for LegacyBackend {
+        fn name(&self) -> &'static str {
+            "legacy"
+        }
+
+        fn write_job(&self, config: &LaunchdConfig) -> Result<String, String> {
+            self.rewritten.lock().unwrap().push(config.schedule_id.clone());
+            Ok(String::new())
+        }
+
+        fn remove_job(&self, _tool: &str, _schedule_id: &str) -> Result<String, String> {
+            Ok(String::new())
+        }
+
+        fn legacy_schedules(&self) -> Result<Vec<RegisteredSchedule>, String> {
+            Ok(self.legacy.clone())
+        }
+
+        fn load_job(&self, _tool: &str, _schedule_id: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn unload_job(&self, _tool: &str, _schedule_id: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn start_job(&self, _tool: &str, _schedule_id: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn installed_calendar(&self, _tool: &str, _schedule_id: &str) -> Result<Vec<CalendarInterval>, String> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn schedule(schedule_id: &str, title: &str) -> RegisteredSchedule {
+        serde_json::from_value(serde_json::json!({
+            "tool": "claude",
+            "schedule_id": schedule_id,
+            "title": title,
+            "execution_time": ["09:00"],
+            "target_directory": "/tmp",
+            "command_args": "run",
+            "created_at": "2025-01-01T09:00:00+09:00",
+            "schedule_type": "daily",
+            "interval_value": null,
+            "start_date": null,
+        }))
+        .unwrap()
+    }
+
+    /// Store path in a fresh directory, removed again when the test ends
+    struct TempStore(PathBuf);
+
+    impl TempStore {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("tauri-cli-scheduler-store-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempStore(dir.join(STORE_FILE))
+        }
+    }
+
+    impl Drop for TempStore {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(self.0.parent().unwrap());
+        }
+    }
+
+    #[test]
+    fn saves_replaces_and_removes_schedules() {
+        let store = TempStore::new("round-trip");
+        let backend = LegacyBackend::default();
+
+        save_schedule_at(&store.0, &backend, schedule("1", "first")).unwrap();
+        save_schedule_at(&store.0, &backend, schedule("2", "second")).unwrap();
+        let edited = RegisteredSchedule {
+            created_at: "2030-01-01T00:00:00+09:00".to_string(),
+            updated_at: Some("2025-02-01T09:00:00+09:00".to_string()),
+            last_modified_by: Some("ui".to_string()),
+            ..schedule("1", "edited")
+        };
+        save_schedule_at(&store.0, &backend, edited).unwrap();
+        // An automatic rewrite keeps the last edit's stamp
+        save_schedule_at(&store.0, &backend, schedule("1", "rewritten")).unwrap();
+
+        let schedules = load_schedules_at(&store.0, &backend).unwrap();
+        assert_eq!(schedules.len(), 2);
+        assert_eq!(schedules[0].title, "rewritten");
+        assert_eq!(schedules[0].created_at, "2025-01-01T09:00:00+09:00");
+        assert_eq!(schedules[0].updated_at.as_deref(), Some("2025-02-01T09:00:00+09:00"));
+        assert_eq!(schedules[0].last_modified_by.as_deref(), Some("ui"));
+
+        assert!(remove_schedule_at(&store.0, &backend, "claude", "2").unwrap());
+        assert!(!remove_schedule_at(&store.0, &backend, "claude", "2").unwrap());
+        assert!(!remove_schedule_at(&store.0, &backend, "codex", "1").unwrap());
+        let schedules = load_schedules_at(&store.0, &backend).unwrap();
+        assert_eq!(schedules.iter().map(|s| s.schedule_id.as_str()).collect::<Vec<_>>(), vec!["1"]);
+        assert!(!store.0.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn migrates_legacy_jobs_once() {
+        let store = TempStore::new("migrate");
+        let backend = LegacyBackend {
+            legacy: vec![schedule("1", "first"), schedule("2", "second")],
+            ..Default::default()
+        };
+        // Before the migration the jobs are the source of truth
+        assert_eq!(load_schedules_at(&store.0, &backend).unwrap().len(), 2);
+        assert!(!remove_schedule_at(&store.0, &backend, "claude", "1").unwrap());
+
+        assert_eq!(migrate_at(&store.0, &backend).unwrap(), 2);
+        assert_eq!(*backend.rewritten.lock().unwrap(), vec!["1".to_string(), "2".to_string()]);
+        let content: serde_json::Value = serde_json::from_str(&fs::read_to_string(&store.0).unwrap()).unwrap();
+        assert_eq!(content["version"], STORE_VERSION);
+        let schedules = load_schedules_at(&store.0, &LegacyBackend::default()).unwrap();
+        assert_eq!(schedules.len(), 2);
+        assert!(schedules.iter().all(|s| s.last_modified_by.as_deref() == Some("import")));
+
+        assert_eq!(migrate_at(&store.0, &backend).unwrap(), 0);
+        assert_eq!(backend.rewritten.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejects_a_newer_store_version() {
+        let store = TempStore::new("version");
+        fs::write(&store.0, r#"{"version": 2, "schedules": []}"#).unwrap();
+        let err = load_schedules_at(&store.0, &LegacyBackend::default()).unwrap_err();
+        assert!(err.contains("version 2"), "{}", err);
+    }
+}
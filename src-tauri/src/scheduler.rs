@@ -0,0 +1,420 @@
+use crate::crontab_manager;
+use crate::in_process_manager;
+use crate::plist_manager::{self, CalendarInterval, LaunchdConfig, RegisteredSchedule};
+use crate::schedule_store;
+use crate::systemd_manager;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Environment variable selecting the scheduler backend ("launchd", "systemd", "crontab"
+/// or "in-process").
+/// Every job is written with it set, so the run scripts and the `check-due` /
+/// `run-followups` subcommands talk to the backend that registered the job.
+pub const BACKEND_ENV: &str = "TAURI_CLI_SCHEDULER_BACKEND";
+
+/// Environment variable relocating the config directory (schedules, logs and history).
+/// Every job is written with the resolved directory, so the run scripts use the same one.
+pub const ROOT_ENV: &str = "TAURI_CLI_SCHEDULER_ROOT";
+
+/// Environment variable pointing the run scripts at the app's binary. Like the run script
+/// path, it depends on where the app is installed rather than on the schedule.
+pub const SCHEDULER_BIN_ENV: &str = "SCHEDULER_BIN";
+
+/// Environment variable relocating the directory jobs are installed to: LaunchAgents for
+/// launchd, the systemd user unit directory for systemd
+pub const AGENTS_DIR_ENV: &str = "TAURI_CLI_SCHEDULER_AGENTS_DIR";
+
+/// Directory set in `var`, if any. Jobs refer to it from other working directories, so
+/// it must be absolute.
+pub fn dir_override(var: &str) -> Result<Option<PathBuf>, String> {
+    match std::env::var_os(var) {
+        Some(dir) if !dir.is_empty() => {
+            let dir = PathBuf::from(dir);
+            if dir.is_absolute() {
+                Ok(Some(dir))
+            } else {
+                Err(format!("{} must be an absolute path: {}", var, dir.display()))
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+/// A place where a schedule's job does not match the schedule store, found by
+/// `reconcile_schedules`
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleDrift {
+    pub tool: String,
+    pub schedule_id: String,
+    pub kind: String, // e.g. "missing-config" or "orphan-job"; see `reconcile_schedules`
+    pub repaired: bool,
+    pub error: Option<String>, // why the repair failed
+}
+
+/// Drift kind of an installed job compared with the `expected` one: `None` when they
+/// match, "install-differs" when they only differ in what `portable` clears (the paths of
+/// the app's binary and run script, which change when the app moves), else `differs`
+pub(crate) fn job_drift<T: PartialEq>(
+    installed: &T,
+    expected: &T,
+    portable: impl Fn(&T) -> T,
+    differs: &'static str,
+) -> Option<&'static str> {
+    if installed == expected {
+        None
+    } else if portable(installed) == portable(expected) {
+        Some("install-differs")
+    } else {
+        Some(differs)
+    }
+}
+
+/// One report entry per kind of drift found for a schedule
+pub(crate) fn drift_entries(
+    (tool, schedule_id): &(String, String),
+    kinds: &[&str],
+    repair: Option<Result<(), String>>,
+) -> Vec<ScheduleDrift> {
+    kinds
+        .iter()
+        .map(|kind| ScheduleDrift {
+            tool: tool.clone(),
+            schedule_id: schedule_id.clone(),
+            kind: kind.to_string(),
+            repaired: matches!(repair, Some(Ok(()))),
+            error: repair.clone().and_then(Result::err),
+        })
+        .collect()
+}
+
+/// The job scheduler schedules are registered with. Schedules themselves live in the
+/// schedule store; a backend only writes, loads and removes the jobs generated from them.
+pub trait SchedulerBackend: Sync {
+    /// Name recorded in each job's environment as `BACKEND_ENV`
+    fn name(&self) -> &'static str;
+
+    /// Write the job for a schedule and load it, or leave it unloaded when paused
+    fn write_job(&self, config: &LaunchdConfig) -> Result<String, String>;
+
+    /// Unload a schedule's job and remove everything written for it
+    fn remove_job(&self, tool: &str, schedule_id: &str) -> Result<String, String>;
+
+    /// Schedules read back from jobs that still carry the whole schedule in their
+    /// environment, as written before the schedule store existed
+    fn legacy_schedules(&self) -> Result<Vec<RegisteredSchedule>, String>;
+
+    /// Hand a written job to the OS scheduler so it fires on its trigger
+    fn load_job(&self, tool: &str, schedule_id: &str) -> Result<(), String>;
+
+    /// Stop a job from firing while keeping its definition
+    fn unload_job(&self, tool: &str, schedule_id: &str) -> Result<(), String>;
+
+    /// Start a loaded job immediately, outside its own trigger
+    fn start_job(&self, tool: &str, schedule_id: &str) -> Result<(), String>;
+
+    /// Calendar triggers the job is currently written with
+    fn installed_calendar(&self, tool: &str, schedule_id: &str) -> Result<Vec<CalendarInterval>, String>;
+
+    /// Compare the jobs written and loaded with the schedule store, and with `repair`
+    /// rewrite or remove the ones that drifted. Backends without copies of their jobs
+    /// outside the store have nothing to compare. Every backend reports the same kinds:
+    /// "missing-config" / "config-differs" for the job definition (plist, service unit,
+    /// env file), "missing-agent" / "agent-differs" for its trigger (LaunchAgents plist,
+    /// timer unit, crontab lines), "install-differs", "not-loaded", "paused-but-loaded",
+    /// "orphan-file" and "orphan-job".
+    fn reconcile_schedules(&self, _repair: bool) -> Result<Vec<ScheduleDrift>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Write a schedule's job and save the schedule to the store, all or nothing: when
+    /// either step fails, the job is rolled back to the schedule's previous definition,
+    /// or removed again for a new schedule
+    fn create_schedule(&self, config: &LaunchdConfig) -> Result<String, String> {
+        let previous = schedule_store::get_schedule(&config.tool, &config.schedule_id)?;
+        let err = match self
+            .write_job(config)
+            .and_then(|message| schedule_store::save_schedule(config.to_registered()).map(|_| message))
+        {
+            Ok(message) => return Ok(message),
+            Err(err) => err,
+        };
+
+        let rollback = match &previous {
+            Some(schedule) => LaunchdConfig::from_registered(schedule)
+                .and_then(|previous| self.write_job(&previous))
+                .map(|_| ()),
+            None => {
+                // Fails when the job was never written; `reconcile_schedules` reports
+                // anything this leaves behind
+                let _ = self.remove_job(&config.tool, &config.schedule_id);
+                Ok(())
+            }
+        };
+        match rollback {
+            Ok(()) => Err(err),
+            Err(rollback_err) => Err(format!("{} (rollback failed: {})", err, rollback_err)),
+        }
+    }
+
+    /// Remove a schedule's job and drop it from the store
+    fn delete_schedule(&self, tool: &str, schedule_id: &str) -> Result<String, String> {
+        let job = self.remove_job(tool, schedule_id);
+        let removed = schedule_store::remove_schedule(tool, schedule_id)?;
+        match job {
+            Ok(message) => Ok(message),
+            // The schedule is gone even when its job already was
+            Err(_) if removed => Ok(format!("Schedule deleted: {}", schedule_id)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Get all registered schedules
+    fn get_registered_schedules(&self) -> Result<Vec<RegisteredSchedule>, String> {
+        schedule_store::load_schedules()
+    }
+
+    /// Load a single schedule by tool and ID
+    fn get_registered_schedule(&self, tool: &str, schedule_id: &str) -> Result<Option<RegisteredSchedule>, String> {
+        schedule_store::get_schedule(tool, schedule_id)
+    }
+
+    /// Pause or resume a schedule. Paused schedules keep their definition and metadata
+    /// but are not loaded.
+    fn set_schedule_enabled(
+        &self,
+        tool: &str,
+        schedule_id: &str,
+        enabled: bool,
+        modified_by: &str,
+    ) -> Result<(), String> {
+        let schedule = self
+            .get_registered_schedule(tool, schedule_id)?
+            .ok_or_else(|| format!("Schedule not found: {}", schedule_id))?;
+        let mut config = LaunchdConfig::from_registered(&schedule)?;
+        config.enabled = enabled;
+        config.modified_by = Some(modified_by.to_string());
+        self.create_schedule(&config)?;
+        Ok(())
+    }
+
+    /// Rewrite a zoned schedule's job when its local fire times no longer match the
+    /// current UTC offsets. Returns whether the job was rewritten.
+    fn refresh_zoned_schedule(&self, schedule: &RegisteredSchedule) -> Result<bool, String> {
+        if crate::occurrence::parse_timezone(&schedule.timezone).is_none() {
+            return Ok(false);
+        }
+        let config = LaunchdConfig::from_registered(schedule)?;
+        if config.schedule_type == "chain"
+            || (config.schedule_type == "interval"
+                && plist_manager::sub_daily_interval_minutes(&config.interval_unit, config.interval_value)
+                    .is_some())
+        {
+            return Ok(false);
+        }
+        let expected = plist_manager::calendar_intervals(&config)?;
+        if self.installed_calendar(&schedule.tool, &schedule.schedule_id)? == expected {
+            return Ok(false);
+        }
+        self.create_schedule(&config)?;
+        Ok(true)
+    }
+}
+
+/// The backend selected by `BACKEND_ENV`. Defaults to launchd on macOS, systemd user
+/// timers where a systemd user session is running, and the crontab elsewhere.
+pub fn backend() -> &'static dyn SchedulerBackend {
+    match std::env::var(BACKEND_ENV).as_deref() {
+        Ok("launchd") => &plist_manager::LaunchdBackend,
+        Ok("systemd") => &systemd_manager::SystemdBackend,
+        Ok("crontab") => &crontab_manager::CrontabBackend,
+        Ok("in-process") => &in_process_manager::InProcessBackend,
+        _ if cfg!(target_os = "macos") => &plist_manager::LaunchdBackend,
+        _ if systemd_manager::has_user_session() => &systemd_manager::SystemdBackend,
+        _ => &crontab_manager::CrontabBackend,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::ffi::{OsStr, OsString};
+    use std::sync::{Mutex, MutexGuard};
+
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Serializes the tests that read or set the scheduler's environment variables, and
+    /// puts back the previous values of the ones it set when dropped
+    pub(crate) struct ScopedEnv {
+        saved: Vec<(&'static str, Option<OsString>)>,
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl ScopedEnv {
+        pub(crate) fn set(vars: &[(&'static str, &OsStr)]) -> Self {
+            // A failed test poisons the lock, which does not matter to the next one
+            let lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let saved = vars
+                .iter()
+                .map(|(key, value)| {
+                    let previous = std::env::var_os(key);
+                    std::env::set_var(key, value);
+                    (*key, previous)
+                })
+                .collect();
+            ScopedEnv { saved, _lock: lock }
+        }
+    }
+
+    impl Drop for ScopedEnv {
+        fn drop(&mut self) {
+            for (key, previous) in self.saved.iter().rev() {
+                match previous {
+                    Some(value) => std::env::set_var(key, value),
+                    None => std::env::remove_var(key),
+                }
+            }
+        }
+    }
+
+    /// A scratch config directory with stand-ins for the OS scheduler's commands first on
+    /// PATH, removed again when dropped. Each stand-in is a shell script body that can call
+    /// `fail_once`, which succeeds once after `fail_next`.
+    pub(crate) struct FakeScheduler {
+        pub(crate) root: PathBuf,
+        _env: ScopedEnv,
+    }
+
+    impl FakeScheduler {
+        pub(crate) fn new(name: &str, backend: &'static str, commands: &[(&str, &str)]) -> Self {
+            use std::os::unix::fs::PermissionsExt;
+
+            let root = std::env::temp_dir().join(format!("tauri-cli-scheduler-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&root);
+            let bin = root.join("bin");
+            std::fs::create_dir_all(&bin).unwrap();
+            for (command, body) in commands {
+                let path = bin.join(command);
+                let script = format!(
+                    "#!/bin/sh\nfail_once() {{ [ -e '{0}/fail' ] && rm '{0}/fail'; }}\n{1}\n",
+                    root.display(),
+                    body.replace("$ROOT", &root.to_string_lossy())
+                );
+                std::fs::write(&path, script).unwrap();
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            }
+
+            let mut path = bin.into_os_string();
+            if let Some(system_path) = std::env::var_os("PATH") {
+                path.push(":");
+                path.push(system_path);
+            }
+            let agents = root.join("agents");
+            let env = ScopedEnv::set(&[
+                (ROOT_ENV, root.join("config").as_os_str()),
+                (AGENTS_DIR_ENV, agents.as_os_str()),
+                (BACKEND_ENV, OsStr::new(backend)),
+                ("PATH", &path),
+            ]);
+            FakeScheduler { root, _env: env }
+        }
+
+        /// Make the next `fail_once` in a stand-in succeed, so the command fails
+        pub(crate) fn fail_next(&self) {
+            std::fs::write(self.root.join("fail"), "").unwrap();
+        }
+    }
+
+    impl Drop for FakeScheduler {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    /// Writes jobs into memory and fails to load the ones titled "broken"
+    #[derive(Default)]
+    struct FlakyBackend {
+        jobs: Mutex<Vec<(String, String)>>, // schedule ID and title of every job written
+        removed: Mutex<Vec<String>>,
+    }
+
+    impl SchedulerBackend for FlakyBackend {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn write_job(&self, config: &LaunchdConfig) -> Result<String, String> {
+            self.jobs
+                .lock()
+                .unwrap()
+                .push((config.schedule_id.clone(), config.title.clone()));
+            if config.title == "broken" {
+                return Err("bootstrap failed".to_string());
+            }
+            Ok(String::new())
+        }
+
+        fn remove_job(&self, _tool: &str, schedule_id: &str) -> Result<String, String> {
+            self.removed.lock().unwrap().push(schedule_id.to_string());
+            Ok(String::new())
+        }
+
+        fn legacy_schedules(&self) -> Result<Vec<RegisteredSchedule>, String> {
+            Ok(Vec::new())
+        }
+
+        fn load_job(&self, _tool: &str, _schedule_id: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn unload_job(&self, _tool: &str, _schedule_id: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn start_job(&self, _tool: &str, _schedule_id: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn installed_calendar(&self, _tool: &str, _schedule_id: &str) -> Result<Vec<CalendarInterval>, String> {
+            Ok(Vec::new())
+        }
+    }
+
+    pub(crate) fn config(schedule_id: &str, title: &str) -> LaunchdConfig {
+        let schedule: RegisteredSchedule = serde_json::from_value(serde_json::json!({
+            "tool": "claude",
+            "schedule_id": schedule_id,
+            "title": title,
+            "execution_time": ["09:00"],
+            "target_directory": "/tmp",
+            "command_args": "run",
+            "created_at": "",
+            "schedule_type": "daily",
+            "interval_value": null,
+            "start_date": null,
+        }))
+        .unwrap();
+        LaunchdConfig::from_registered(&schedule).unwrap()
+    }
+
+    #[test]
+    fn failed_registration_rolls_back() {
+        let root = std::env::temp_dir().join(format!("tauri-cli-scheduler-test-{}", std::process::id()));
+        // The store falls back to this backend's jobs while it does not exist yet
+        let _env = ScopedEnv::set(&[(ROOT_ENV, root.as_os_str()), (BACKEND_ENV, OsStr::new("in-process"))]);
+        let backend = FlakyBackend::default();
+
+        backend.create_schedule(&config("1", "first")).unwrap();
+        assert!(backend.create_schedule(&config("1", "broken")).is_err());
+        assert_eq!(
+            backend.jobs.lock().unwrap().last(),
+            Some(&("1".to_string(), "first".to_string()))
+        );
+        assert_eq!(schedule_store::get_schedule("claude", "1").unwrap().unwrap().title, "first");
+
+        assert!(backend.create_schedule(&config("2", "broken")).is_err());
+        assert_eq!(*backend.removed.lock().unwrap(), vec!["2".to_string()]);
+        assert!(schedule_store::get_schedule("claude", "2").unwrap().is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
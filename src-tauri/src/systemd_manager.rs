@@ -0,0 +1,601 @@
+use crate::plist_manager::{self, CalendarInterval, LaunchdConfig, RegisteredSchedule};
+use crate::scheduler::{drift_entries, job_drift, ScheduleDrift, SchedulerBackend, SCHEDULER_BIN_ENV};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Schedules registered as systemd user units: a `.service` running the tool's script
+/// with the schedule's ID in its environment, and a `.timer` carrying its triggers
+pub struct SystemdBackend;
+
+const UNIT_PREFIX: &str = "tauri-cli-scheduler-";
+const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+/// Written to the [Unit] section of a paused schedule's service (systemd ignores X- keys)
+const PAUSED_KEY: &str = "X-TauriCliSchedulerPaused";
+
+/// Whether a systemd user manager is running for this user (not the case on most CI
+/// runners and containers)
+pub fn has_user_session() -> bool {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(|dir| Path::new(&dir).join("systemd/private").exists())
+        .unwrap_or(false)
+}
+
+/// Get the systemd user unit directory, relocatable through `AGENTS_DIR_ENV` (which then
+/// has to be on systemd's unit search path)
+fn get_units_dir() -> Result<PathBuf, String> {
+    if let Some(dir) = crate::scheduler::dir_override(crate::scheduler::AGENTS_DIR_ENV)? {
+        return Ok(dir);
+    }
+    dirs::config_dir()
+        .ok_or("Could not determine config directory".to_string())
+        .map(|p| p.join("systemd/user"))
+}
+
+/// Unit name of a schedule, without the .service/.timer suffix
+fn unit_name(tool: &str, schedule_id: &str) -> String {
+    format!("{}{}-{}", UNIT_PREFIX, tool, schedule_id)
+}
+
+fn get_unit_path(tool: &str, schedule_id: &str, suffix: &str) -> Result<PathBuf, String> {
+    Ok(get_units_dir()?.join(format!("{}.{}", unit_name(tool, schedule_id), suffix)))
+}
+
+fn parse_service_filename(file_name: &str) -> Option<(String, String)> {
+    parse_unit_name(file_name.strip_suffix(".service")?)
+}
+
+/// Tool and schedule ID of a unit name without its suffix
+fn parse_unit_name(name: &str) -> Option<(String, String)> {
+    let (tool, schedule_id) = name.strip_prefix(UNIT_PREFIX)?.split_once('-')?;
+    Some((tool.to_string(), schedule_id.to_string()))
+}
+
+/// Tool and schedule ID of every schedule with a service or timer unit in `dir`
+fn schedule_units_in(dir: &Path) -> Result<BTreeSet<(String, String)>, String> {
+    if !dir.exists() {
+        return Ok(BTreeSet::new());
+    }
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read systemd user unit directory: {}", e))?;
+    let mut units = BTreeSet::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read unit entry: {}", e))?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+        if let Some(ids) = file_name
+            .strip_suffix(".service")
+            .or_else(|| file_name.strip_suffix(".timer"))
+            .and_then(parse_unit_name)
+        {
+            units.insert(ids);
+        }
+    }
+    Ok(units)
+}
+
+fn systemctl(args: &[&str]) -> Result<(), String> {
+    systemctl_output(args).map(|_| ())
+}
+
+fn systemctl_output(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run systemctl: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("systemctl {} failed: {}", args.join(" "), stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Tool and schedule ID of every schedule whose timer is enabled
+fn enabled_timers() -> Result<BTreeSet<(String, String)>, String> {
+    let pattern = format!("{}*.timer", UNIT_PREFIX);
+    let output = systemctl_output(&["list-unit-files", "--state=enabled", "--no-legend", &pattern])?;
+    Ok(output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter_map(|unit| parse_unit_name(unit.strip_suffix(".timer")?))
+        .collect())
+}
+
+/// Escape `%` specifiers, which systemd expands in most unit settings
+fn escape_specifiers(value: &str) -> String {
+    value.replace('%', "%%")
+}
+
+/// Double-quoted word for ExecStart= and Environment=
+fn quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '%' => quoted.push_str("%%"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Inverse of `quote`
+fn unquote(word: &str) -> Option<String> {
+    let inner = word.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                escaped => value.push(escaped),
+            },
+            '%' if chars.peek() == Some(&'%') => {
+                chars.next();
+                value.push('%');
+            }
+            c => value.push(c),
+        }
+    }
+    Some(value)
+}
+
+/// `OnCalendar=` expression for a launchd-style calendar entry
+fn on_calendar(entry: &CalendarInterval) -> String {
+    let field = |value: Option<u32>| value.map_or("*".to_string(), |value| format!("{:02}", value));
+    let spec = format!(
+        "*-{}-{} {}:{}:00",
+        field(entry.month),
+        field(entry.day),
+        field(entry.hour),
+        field(entry.minute)
+    );
+    match entry.weekday {
+        Some(weekday) => format!("{} {}", WEEKDAY_NAMES[weekday as usize % 7], spec),
+        None => spec,
+    }
+}
+
+/// Parse an `OnCalendar=` expression written by `on_calendar`
+fn parse_on_calendar(spec: &str) -> Option<CalendarInterval> {
+    let mut parts: Vec<&str> = spec.split_whitespace().collect();
+    let weekday = if parts.len() == 3 {
+        let name = parts.remove(0);
+        Some(WEEKDAY_NAMES.iter().position(|weekday| *weekday == name)? as u32)
+    } else {
+        None
+    };
+    let &[date, time] = parts.as_slice() else {
+        return None;
+    };
+    let field = |value: &str| -> Option<Option<u32>> {
+        if value == "*" {
+            Some(None)
+        } else {
+            value.parse::<u32>().ok().map(Some)
+        }
+    };
+    let &[_, month, day] = date.split('-').collect::<Vec<_>>().as_slice() else {
+        return None;
+    };
+    let &[hour, minute, _] = time.split(':').collect::<Vec<_>>().as_slice() else {
+        return None;
+    };
+    Some(CalendarInterval {
+        minute: field(minute)?,
+        hour: field(hour)?,
+        day: field(day)?,
+        weekday,
+        month: field(month)?,
+    })
+}
+
+/// [Timer] triggers of a schedule; `None` for chained schedules, which have no trigger of
+/// their own and are started by the upstream run
+fn timer_triggers(config: &LaunchdConfig) -> Result<Option<Vec<String>>, String> {
+    if config.schedule_type == "chain" {
+        return Ok(None);
+    }
+    let sub_daily_minutes = if config.schedule_type == "interval" {
+        plist_manager::sub_daily_interval_minutes(&config.interval_unit, config.interval_value)
+    } else {
+        None
+    };
+    if let Some(minutes) = sub_daily_minutes {
+        // Same as launchd's StartInterval; the active window is checked in the script
+        if minutes == 0 {
+            return Err("Interval must be at least one minute".to_string());
+        }
+        return Ok(Some(vec![
+            format!("OnActiveSec={}min", minutes),
+            format!("OnUnitActiveSec={}min", minutes),
+        ]));
+    }
+    Ok(Some(
+        plist_manager::calendar_intervals(config)?
+            .iter()
+            .map(|entry| format!("OnCalendar={}", on_calendar(entry)))
+            .collect(),
+    ))
+}
+
+/// Content of a schedule's service unit
+fn service_unit(config: &LaunchdConfig) -> Result<String, String> {
+    let log_dir = plist_manager::ensure_config_dir()?.join("logs");
+    let script_path = plist_manager::run_script_path(&config.tool)?;
+    let mut service = vec![
+        "[Unit]".to_string(),
+        format!(
+            "Description=tauri-cli-scheduler: {} ({})",
+            escape_specifiers(&config.title.replace('\n', " ")),
+            config.tool
+        ),
+    ];
+    if !config.enabled {
+        service.push(format!("{}=true", PAUSED_KEY));
+    }
+    service.push(String::new());
+    service.push("[Service]".to_string());
+    // No start timeout: a jittered run may sleep before launching the tool
+    service.push("Type=oneshot".to_string());
+    service.push(format!(
+        "ExecStart=/bin/bash {}",
+        quote(&script_path.to_string_lossy())
+    ));
+    for (key, value) in plist_manager::job_environment(config, SystemdBackend.name()) {
+        service.push(format!("Environment={}", quote(&format!("{}={}", key, value))));
+    }
+    service.push(format!(
+        "StandardOutput=append:{}",
+        escape_specifiers(&log_dir.join(format!("{}.log", config.tool)).to_string_lossy())
+    ));
+    service.push(format!(
+        "StandardError=append:{}",
+        escape_specifiers(&log_dir.join(format!("{}.error.log", config.tool)).to_string_lossy())
+    ));
+    service.push(String::new());
+    Ok(service.join("\n"))
+}
+
+/// A service unit without the lines that depend on where the app is installed
+fn portable_service_unit(unit: &str) -> String {
+    let scheduler_bin = format!("Environment=\"{}=", SCHEDULER_BIN_ENV);
+    unit.lines()
+        .filter(|line| !line.starts_with("ExecStart=") && !line.starts_with(&scheduler_bin))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Content of a schedule's timer unit; `None` for chained schedules
+fn timer_unit(config: &LaunchdConfig) -> Result<Option<String>, String> {
+    let Some(triggers) = timer_triggers(config)? else {
+        return Ok(None);
+    };
+    let mut timer = vec![
+        "[Unit]".to_string(),
+        format!("Description=Timer for {}", unit_name(&config.tool, &config.schedule_id)),
+        String::new(),
+        "[Timer]".to_string(),
+    ];
+    timer.extend(triggers);
+    timer.push("WakeSystem=true".to_string());
+    timer.push(String::new());
+    timer.push("[Install]".to_string());
+    timer.push("WantedBy=timers.target".to_string());
+    timer.push(String::new());
+    Ok(Some(timer.join("\n")))
+}
+
+/// Load a schedule from a service unit written before the schedule store
+fn load_service_from_path(service_path: &Path) -> Result<Option<RegisteredSchedule>, String> {
+    if !service_path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(service_path)
+        .map_err(|e| format!("Failed to read service unit: {}", e))?;
+
+    let mut env = HashMap::new();
+    let mut enabled = true;
+    for line in content.lines().map(str::trim) {
+        if let Some(assignment) = line.strip_prefix("Environment=").and_then(unquote) {
+            if let Some((key, value)) = assignment.split_once('=') {
+                env.insert(key.to_string(), value.to_string());
+            }
+        } else if line == format!("{}=true", PAUSED_KEY) {
+            enabled = false;
+        }
+    }
+    let fallback_ids = service_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .and_then(parse_service_filename);
+
+    Ok(plist_manager::schedule_from_environment(
+        &env,
+        fallback_ids,
+        enabled,
+    ))
+}
+
+impl SchedulerBackend for SystemdBackend {
+    fn name(&self) -> &'static str {
+        "systemd"
+    }
+
+    /// Units are written in place, since systemd only loads them from its unit path; a
+    /// failed load is undone by `create_schedule` rewriting the previous definition
+    fn write_job(&self, config: &LaunchdConfig) -> Result<String, String> {
+        let units_dir = get_units_dir()?;
+        fs::create_dir_all(&units_dir)
+            .map_err(|e| format!("Failed to create systemd user unit directory: {}", e))?;
+        let service_path = get_unit_path(&config.tool, &config.schedule_id, "service")?;
+        let timer_path = get_unit_path(&config.tool, &config.schedule_id, "timer")?;
+        let timer = timer_unit(config)?;
+
+        fs::write(&service_path, service_unit(config)?)
+            .map_err(|e| format!("Failed to write service unit: {}", e))?;
+
+        match timer {
+            Some(timer) => {
+                fs::write(&timer_path, timer)
+                    .map_err(|e| format!("Failed to write timer unit: {}", e))?;
+            }
+            None => {
+                // A schedule turned into a chain keeps no timer of its own
+                if timer_path.exists() {
+                    let _ = self.unload_job(&config.tool, &config.schedule_id);
+                    fs::remove_file(&timer_path)
+                        .map_err(|e| format!("Failed to delete timer unit: {}", e))?;
+                }
+            }
+        }
+        systemctl(&["daemon-reload"])?;
+
+        if !config.enabled {
+            self.unload_job(&config.tool, &config.schedule_id)?;
+            return Ok(format!("Units saved at: {} (paused)", service_path.display()));
+        }
+
+        self.load_job(&config.tool, &config.schedule_id)?;
+
+        Ok(format!(
+            "Units created at: {} and enabled via systemctl --user",
+            service_path.display()
+        ))
+    }
+
+    fn remove_job(&self, tool: &str, schedule_id: &str) -> Result<String, String> {
+        let service_path = get_unit_path(tool, schedule_id, "service")?;
+        let timer_path = get_unit_path(tool, schedule_id, "timer")?;
+
+        if timer_path.exists() {
+            let _ = self.unload_job(tool, schedule_id);
+            fs::remove_file(&timer_path)
+                .map_err(|e| format!("Failed to delete timer unit: {}", e))?;
+        }
+
+        if service_path.exists() {
+            fs::remove_file(&service_path)
+                .map_err(|e| format!("Failed to delete service unit: {}", e))?;
+            let _ = systemctl(&["daemon-reload"]);
+            Ok(format!("Units deleted: {}", service_path.display()))
+        } else {
+            Err(format!("Units not found for tool: {}", tool))
+        }
+    }
+
+    /// Every service unit of ours in the user unit directory
+    fn legacy_schedules(&self) -> Result<Vec<RegisteredSchedule>, String> {
+        let units_dir = get_units_dir()?;
+        let mut schedules = Vec::new();
+
+        if !units_dir.exists() {
+            return Ok(schedules);
+        }
+
+        let entries = fs::read_dir(&units_dir)
+            .map_err(|e| format!("Failed to read systemd user unit directory: {}", e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read unit entry: {}", e))?;
+            let path = entry.path();
+            if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+                if parse_service_filename(file_name).is_none() {
+                    continue;
+                }
+                if let Ok(Some(schedule)) = load_service_from_path(&path) {
+                    schedules.push(schedule);
+                }
+            }
+        }
+
+        Ok(schedules)
+    }
+
+    /// Enable and start the schedule's timer; chained schedules have none
+    fn load_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        if !get_unit_path(tool, schedule_id, "timer")?.exists() {
+            return Ok(());
+        }
+        systemctl(&["enable", "--now", &format!("{}.timer", unit_name(tool, schedule_id))])
+    }
+
+    /// Disable and stop the timer, keeping both units on disk
+    fn unload_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        if !get_unit_path(tool, schedule_id, "timer")?.exists() {
+            return Ok(());
+        }
+        systemctl(&["disable", "--now", &format!("{}.timer", unit_name(tool, schedule_id))])
+    }
+
+    fn start_job(&self, tool: &str, schedule_id: &str) -> Result<(), String> {
+        // Do not wait for the oneshot service; the caller is usually another schedule's run
+        systemctl(&[
+            "start",
+            "--no-block",
+            &format!("{}.service", unit_name(tool, schedule_id)),
+        ])
+    }
+
+    fn installed_calendar(&self, tool: &str, schedule_id: &str) -> Result<Vec<CalendarInterval>, String> {
+        let timer_path = get_unit_path(tool, schedule_id, "timer")?;
+        Ok(fs::read_to_string(&timer_path)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| line.trim().strip_prefix("OnCalendar="))
+                    .filter_map(parse_on_calendar)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn reconcile_schedules(&self, repair: bool) -> Result<Vec<ScheduleDrift>, String> {
+        let enabled = enabled_timers()?;
+        let unit_files = schedule_units_in(&get_units_dir()?)?;
+        let read_unit = |path: PathBuf| fs::read_to_string(path).ok();
+
+        let mut drifts = Vec::new();
+        let mut known = BTreeSet::new();
+        for schedule in self.get_registered_schedules()? {
+            let ids = (schedule.tool.clone(), schedule.schedule_id.clone());
+            let config = LaunchdConfig::from_registered(&schedule)?;
+            let expected_timer = timer_unit(&config)?;
+
+            let mut kinds = Vec::new();
+            match read_unit(get_unit_path(&ids.0, &ids.1, "service")?) {
+                None => kinds.push("missing-config"),
+                Some(unit) => kinds.extend(job_drift(
+                    &unit,
+                    &service_unit(&config)?,
+                    |unit: &String| portable_service_unit(unit),
+                    "config-differs",
+                )),
+            }
+            match (read_unit(get_unit_path(&ids.0, &ids.1, "timer")?), &expected_timer) {
+                (None, Some(_)) => kinds.push("missing-agent"),
+                (Some(timer), Some(expected)) if timer == *expected => {}
+                (Some(_), _) => kinds.push("agent-differs"),
+                (None, None) => {}
+            }
+            if config.enabled && expected_timer.is_some() && !enabled.contains(&ids) {
+                kinds.push("not-loaded");
+            } else if !config.enabled && enabled.contains(&ids) {
+                kinds.push("paused-but-loaded");
+            }
+
+            let repaired = (repair && !kinds.is_empty()).then(|| self.write_job(&config).map(|_| ()));
+            drifts.extend(drift_entries(&ids, &kinds, repaired));
+            known.insert(ids);
+        }
+
+        let orphans: BTreeSet<_> = unit_files
+            .iter()
+            .chain(&enabled)
+            .filter(|ids| !known.contains(*ids))
+            .collect();
+        for ids in orphans {
+            let mut kinds = Vec::new();
+            if unit_files.contains(ids) {
+                kinds.push("orphan-file");
+            }
+            if enabled.contains(ids) {
+                kinds.push("orphan-job");
+            }
+
+            let repaired = repair.then(|| {
+                if enabled.contains(ids) {
+                    systemctl(&["disable", "--now", &format!("{}.timer", unit_name(&ids.0, &ids.1))])?;
+                }
+                for suffix in ["timer", "service"] {
+                    let path = get_unit_path(&ids.0, &ids.1, suffix)?;
+                    if path.exists() {
+                        fs::remove_file(&path)
+                            .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+                    }
+                }
+                systemctl(&["daemon-reload"])
+            });
+            drifts.extend(drift_entries(ids, &kinds, repaired));
+        }
+        Ok(drifts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn on_calendar_round_trips() {
+        let entries = [
+            CalendarInterval { minute: Some(5), hour: Some(9), ..Default::default() },
+            CalendarInterval { minute: Some(0), hour: Some(18), weekday: Some(1), ..Default::default() },
+            CalendarInterval { minute: Some(30), hour: Some(7), weekday: Some(0), ..Default::default() },
+            CalendarInterval { minute: Some(0), hour: Some(12), day: Some(31), ..Default::default() },
+            CalendarInterval { minute: Some(45), hour: Some(23), day: Some(4), month: Some(7), weekday: None },
+            CalendarInterval::default(),
+        ];
+        for entry in entries {
+            assert_eq!(parse_on_calendar(&on_calendar(&entry)), Some(entry.clone()), "{:?}", entry);
+        }
+        assert_eq!(
+            on_calendar(&CalendarInterval { minute: Some(5), hour: Some(9), weekday: Some(1), ..Default::default() }),
+            "Mon *-*-* 09:05:00"
+        );
+        assert_eq!(
+            on_calendar(&CalendarInterval { minute: Some(0), hour: Some(8), day: Some(1), month: Some(3), weekday: None }),
+            "*-03-01 08:00:00"
+        );
+    }
+
+    #[test]
+    fn parse_on_calendar_rejects_foreign_expressions() {
+        for spec in ["daily", "Funday *-*-* 09:00:00", "*-*-* 09:00", "Mon..Fri *-*-* 09:00:00", "*-*-* 9/2:00:00"] {
+            assert_eq!(parse_on_calendar(spec), None, "{}", spec);
+        }
+    }
+
+    #[test]
+    fn quote_round_trips() {
+        for value in ["", "plain", "with space", "say \"hi\"", "back\\slash", "50% off", "two\nlines", "%%"] {
+            let quoted = quote(value);
+            assert!(!quoted[1..quoted.len() - 1].contains('\n'));
+            assert_eq!(unquote(&quoted).as_deref(), Some(value), "{}", quoted);
+        }
+        assert_eq!(quote("50% \"off\""), "\"50%% \\\"off\\\"\"");
+        assert_eq!(unquote("unquoted"), None);
+    }
+
+    #[test]
+    fn failed_load_rolls_back_to_the_previous_job() {
+        use crate::scheduler::tests::{config, FakeScheduler};
+
+        let fake = FakeScheduler::new(
+            "systemd",
+            "systemd",
+            &[("systemctl", r#"[ "$2" = "enable" ] && fail_once && exit 1; exit 0"#)],
+        );
+        let backend = SystemdBackend;
+        let first = config("1", "first");
+        backend.create_schedule(&first).unwrap();
+
+        let mut moved = config("1", "moved");
+        moved.times = vec![(10, 30)];
+        fake.fail_next();
+        assert!(backend.create_schedule(&moved).is_err());
+        assert_eq!(
+            backend.installed_calendar("claude", "1").unwrap(),
+            plist_manager::calendar_intervals(&first).unwrap()
+        );
+        assert_eq!(backend.get_registered_schedule("claude", "1").unwrap().unwrap().title, "first");
+    }
+}
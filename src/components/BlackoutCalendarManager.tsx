@@ -0,0 +1,164 @@
+import { useState } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
+import { invoke } from "@tauri-apps/api/core";
+import { BlackoutCalendar, BlackoutRange, IcsImport } from "../types/schedule";
+import { Button } from "./ui/Button";
+import { Input, Textarea } from "./ui/Input";
+
+interface BlackoutCalendarManagerProps {
+  calendars: BlackoutCalendar[];
+  onChange: () => void;
+  disabled?: boolean;
+}
+
+const DATE_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
+
+// One entry per line: "2025-01-01" or "2025-12-29~2026-01-03"
+const parseEntries = (text: string, yearly: boolean) => {
+  const dates: string[] = [];
+  const ranges: BlackoutRange[] = [];
+  for (const line of text.split("\n").map((l) => l.trim()).filter(Boolean)) {
+    const [start, end] = line.split("~").map((part) => part.trim());
+    if (!DATE_PATTERN.test(start) || (end !== undefined && !DATE_PATTERN.test(end))) {
+      throw new Error(`日付の形式が正しくありません: ${line}`);
+    }
+    if (end === undefined && !yearly) {
+      dates.push(start);
+    } else {
+      ranges.push({ start, end: end ?? start, yearly });
+    }
+  }
+  return { dates, ranges };
+};
+
+const describeCalendar = (calendar: BlackoutCalendar) => {
+  const parts: string[] = [];
+  if (calendar.dates.length > 0) parts.push(`${calendar.dates.length}日`);
+  if (calendar.ranges.length > 0) parts.push(`${calendar.ranges.length}期間`);
+  if (calendar.ranges.some((range) => range.yearly)) parts.push("毎年");
+  return parts.join(" ・ ");
+};
+
+export function BlackoutCalendarManager({
+  calendars,
+  onChange,
+  disabled,
+}: BlackoutCalendarManagerProps) {
+  const [name, setName] = useState("");
+  const [entries, setEntries] = useState("");
+  const [yearly, setYearly] = useState(false);
+  const [message, setMessage] = useState("");
+
+  const handleSave = async () => {
+    try {
+      const { dates, ranges } = parseEntries(entries, yearly);
+      await invoke<BlackoutCalendar>("save_blackout_calendar", {
+        calendar: { id: "", name: name.trim(), dates, ranges },
+      });
+      setName("");
+      setEntries("");
+      setYearly(false);
+      setMessage("除外カレンダーを追加しました");
+      onChange();
+    } catch (error) {
+      setMessage(`エラー: ${error instanceof Error ? error.message : error}`);
+    }
+  };
+
+  const handleImport = async () => {
+    try {
+      const selected = await open({
+        multiple: false,
+        filters: [{ name: "iCalendar", extensions: ["ics"] }],
+        title: "祝日カレンダー（.ics）を選択",
+      });
+      if (!selected || typeof selected !== "string") return;
+      const { calendar, skipped } = await invoke<IcsImport>("import_blackout_ics", {
+        name: name.trim(),
+        path: selected,
+      });
+      setName("");
+      const skippedNote =
+        skipped.length > 0 ? `。対応していない繰り返しの予定を除外しました: ${skipped.join("、")}` : "";
+      setMessage(`「${calendar.name}」をインポートしました（${describeCalendar(calendar)}）${skippedNote}`);
+      onChange();
+    } catch (error) {
+      setMessage(`エラー: ${error}`);
+    }
+  };
+
+  const handleDelete = async (calendarId: string) => {
+    try {
+      await invoke("delete_blackout_calendar", { calendarId });
+      onChange();
+    } catch (error) {
+      setMessage(`エラー: ${error}`);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <p className="text-sm font-medium text-gray-700 dark:text-gray-300">除外カレンダー</p>
+
+      {calendars.length > 0 && (
+        <div className="space-y-2">
+          {calendars.map((calendar) => (
+            <div
+              key={calendar.id}
+              className="flex items-center justify-between rounded-md border border-gray-200 dark:border-gray-700 p-2"
+            >
+              <div>
+                <p className="text-sm text-gray-800 dark:text-gray-100">{calendar.name}</p>
+                <p className="text-xs text-gray-500">{describeCalendar(calendar)}</p>
+              </div>
+              <Button
+                variant="ghost"
+                onClick={() => handleDelete(calendar.id)}
+                disabled={disabled}
+              >
+                削除
+              </Button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <Input
+        type="text"
+        value={name}
+        onChange={(e) => setName(e.target.value)}
+        placeholder="カレンダー名（例: 日本の祝日）"
+        disabled={disabled}
+      />
+      <Textarea
+        value={entries}
+        onChange={(e) => setEntries(e.target.value)}
+        placeholder={"1行に1件: 2025-01-01 または 2025-12-29~2026-01-03"}
+        rows={3}
+        disabled={disabled}
+      />
+      <label className="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300">
+        <input
+          type="checkbox"
+          checked={yearly}
+          onChange={(e) => setYearly(e.target.checked)}
+          disabled={disabled}
+        />
+        毎年繰り返す
+      </label>
+      <div className="flex gap-2">
+        <Button
+          variant="secondary"
+          onClick={handleSave}
+          disabled={disabled || !name.trim() || !entries.trim()}
+        >
+          追加
+        </Button>
+        <Button variant="secondary" onClick={handleImport} disabled={disabled}>
+          .icsをインポート
+        </Button>
+      </div>
+      {message && <p className="text-xs text-gray-500">{message}</p>}
+    </div>
+  );
+}
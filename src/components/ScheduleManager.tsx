@@ -1,10 +1,24 @@
 import { useEffect, useMemo, useState } from "react";
 import { open } from "@tauri-apps/plugin-dialog";
 import { invoke } from "@tauri-apps/api/core";
-import { RegisteredSchedule, ScheduleHistoryEntry, ScheduleResult, ScheduleType } from "../types/schedule";
+import {
+  BlackoutCalendar,
+  ChainTrigger,
+  ChainTriggerStatus,
+  IntervalUnit,
+  MonthlyRule,
+  RegisteredSchedule,
+  ScheduleDrift,
+  ScheduleDriftKind,
+  ScheduleHistoryEntry,
+  ScheduleResult,
+  ScheduleType,
+} from "../types/schedule";
 import { Button } from "./ui/Button";
 import { Input, Textarea } from "./ui/Input";
 import { Select } from "./ui/Select";
+import { Badge } from "./ui/Badge";
+import { BlackoutCalendarManager } from "./BlackoutCalendarManager";
 
 interface ScheduleManagerProps {
   tool: string;
@@ -16,6 +30,347 @@ interface ScheduleManagerProps {
   onScheduleUnregister: (success: boolean) => void;
 }
 
+interface ExecutionTimesInputProps {
+  times: string[];
+  onChange: (times: string[]) => void;
+  disabled?: boolean;
+}
+
+function ExecutionTimesInput({ times, onChange, disabled }: ExecutionTimesInputProps) {
+  const updateTime = (index: number, value: string) => {
+    onChange(times.map((time, i) => (i === index ? value : time)));
+  };
+
+  const removeTime = (index: number) => {
+    onChange(times.filter((_, i) => i !== index));
+  };
+
+  return (
+    <div className="space-y-2">
+      {times.map((time, index) => (
+        <div key={index} className="flex items-center gap-2">
+          <Input
+            type="time"
+            value={time}
+            onChange={(e) => updateTime(index, e.target.value)}
+            disabled={disabled}
+          />
+          {times.length > 1 && (
+            <Button
+              type="button"
+              variant="ghost"
+              onClick={() => removeTime(index)}
+              disabled={disabled}
+            >
+              削除
+            </Button>
+          )}
+        </div>
+      ))}
+      <Button
+        type="button"
+        variant="secondary"
+        onClick={() => onChange([...times, times[times.length - 1] ?? "00:00"])}
+        disabled={disabled}
+      >
+        時刻を追加
+      </Button>
+    </div>
+  );
+}
+
+interface BlackoutCalendarSelectorProps {
+  calendars: BlackoutCalendar[];
+  selected: string[];
+  onChange: (selected: string[]) => void;
+  disabled?: boolean;
+}
+
+function BlackoutCalendarSelector({
+  calendars,
+  selected,
+  onChange,
+  disabled,
+}: BlackoutCalendarSelectorProps) {
+  if (calendars.length === 0) {
+    return <p className="text-xs text-gray-500">除外カレンダーは未登録です</p>;
+  }
+  const toggle = (id: string) => {
+    onChange(selected.includes(id) ? selected.filter((s) => s !== id) : [...selected, id]);
+  };
+  return (
+    <div className="flex flex-wrap gap-3">
+      {calendars.map((calendar) => (
+        <label
+          key={calendar.id}
+          className="flex items-center gap-1 text-sm text-gray-700 dark:text-gray-300"
+        >
+          <input
+            type="checkbox"
+            checked={selected.includes(calendar.id)}
+            onChange={() => toggle(calendar.id)}
+            disabled={disabled}
+          />
+          {calendar.name}
+        </label>
+      ))}
+    </div>
+  );
+}
+
+const WEEKDAY_LABELS = ["日", "月", "火", "水", "木", "金", "土"];
+const BUSINESS_DAYS = [1, 2, 3, 4, 5];
+
+interface WeekdaySelectorProps {
+  weekdays: number[];
+  onChange: (weekdays: number[]) => void;
+  disabled?: boolean;
+}
+
+function WeekdaySelector({ weekdays, onChange, disabled }: WeekdaySelectorProps) {
+  const toggleWeekday = (weekday: number) => {
+    const next = weekdays.includes(weekday)
+      ? weekdays.filter((w) => w !== weekday)
+      : [...weekdays, weekday];
+    onChange(next.sort((a, b) => a - b));
+  };
+
+  return (
+    <div className="flex flex-wrap items-center gap-3">
+      {WEEKDAY_LABELS.map((label, weekday) => (
+        <label key={weekday} className="inline-flex items-center">
+          <input
+            type="checkbox"
+            className="form-checkbox text-primary-DEFAULT"
+            checked={weekdays.includes(weekday)}
+            onChange={() => toggleWeekday(weekday)}
+            disabled={disabled}
+          />
+          <span className="ml-1 text-sm text-gray-700 dark:text-gray-300">{label}</span>
+        </label>
+      ))}
+      <Button
+        type="button"
+        variant="ghost"
+        onClick={() => onChange(BUSINESS_DAYS)}
+        disabled={disabled}
+      >
+        平日
+      </Button>
+    </div>
+  );
+}
+
+const formatWeekdays = (weekdays: number[]) =>
+  weekdays.map((weekday) => WEEKDAY_LABELS[weekday % 7]).join("・");
+
+/** Weekdays of a weekly schedule; older schedules only carry the start date's weekday */
+const getScheduleWeekdays = (schedule: RegisteredSchedule) => {
+  if (schedule.weekdays && schedule.weekdays.length > 0) return schedule.weekdays;
+  if (!schedule.start_date) return [];
+  const startDate = new Date(`${schedule.start_date}T00:00:00`);
+  if (Number.isNaN(startDate.getTime())) return [];
+  return [startDate.getDay()];
+};
+
+const MONTH_WEEK_LABELS: Record<number, string> = {
+  1: "第1",
+  2: "第2",
+  3: "第3",
+  4: "第4",
+  5: "第5",
+  [-1]: "最終",
+};
+
+const DEFAULT_MONTHLY_RULE: MonthlyRule = { mode: "day", day: 1, week: 1, weekday: 1 };
+
+interface MonthlyRuleInputProps {
+  rule: MonthlyRule;
+  onChange: (rule: MonthlyRule) => void;
+  disabled?: boolean;
+}
+
+function MonthlyRuleInput({ rule, onChange, disabled }: MonthlyRuleInputProps) {
+  return (
+    <div className="flex flex-wrap items-end gap-3">
+      <Select
+        label="実行日"
+        value={rule.mode}
+        onChange={(e) => onChange({ ...rule, mode: e.target.value as MonthlyRule["mode"] })}
+        options={[
+          { value: "day", label: "日付を指定" },
+          { value: "last", label: "月末" },
+          { value: "weekday", label: "第n曜日" },
+        ]}
+        disabled={disabled}
+      />
+      {rule.mode === "day" && (
+        <Input
+          type="number"
+          min={1}
+          max={31}
+          value={rule.day}
+          onChange={(e) =>
+            onChange({ ...rule, day: Math.min(31, Math.max(1, parseInt(e.target.value) || 1)) })
+          }
+          disabled={disabled}
+        />
+      )}
+      {rule.mode === "weekday" && (
+        <>
+          <Select
+            value={rule.week}
+            onChange={(e) => onChange({ ...rule, week: parseInt(e.target.value) })}
+            options={[1, 2, 3, 4, 5, -1].map((week) => ({
+              value: week,
+              label: MONTH_WEEK_LABELS[week],
+            }))}
+            disabled={disabled}
+          />
+          <Select
+            value={rule.weekday}
+            onChange={(e) => onChange({ ...rule, weekday: parseInt(e.target.value) })}
+            options={WEEKDAY_LABELS.map((label, weekday) => ({
+              value: weekday,
+              label: `${label}曜日`,
+            }))}
+            disabled={disabled}
+          />
+        </>
+      )}
+    </div>
+  );
+}
+
+const CHAIN_STATUS_LABELS: Record<ChainTriggerStatus, string> = {
+  success: "成功したら",
+  failure: "失敗したら",
+  any: "終了したら（成否を問わず）",
+};
+
+const DEFAULT_CHAIN_TRIGGER: ChainTrigger = { tool: "", scheduleId: "", status: "success" };
+
+interface ChainTriggerInputProps {
+  trigger: ChainTrigger;
+  schedules: RegisteredSchedule[]; // candidates for the upstream schedule, any tool
+  onChange: (trigger: ChainTrigger) => void;
+  disabled?: boolean;
+}
+
+function ChainTriggerInput({ trigger, schedules, onChange, disabled }: ChainTriggerInputProps) {
+  // Schedule IDs are only unique per tool, so options are keyed by both
+  const selectUpstream = (key: string) => {
+    const upstream = schedules.find((schedule) => occurrenceKey(schedule) === key);
+    onChange({ ...trigger, tool: upstream?.tool ?? "", scheduleId: upstream?.schedule_id ?? "" });
+  };
+  return (
+    <div className="flex flex-wrap items-end gap-3">
+      <Select
+        label="先行スケジュール"
+        value={trigger.scheduleId ? `${trigger.tool}:${trigger.scheduleId}` : ""}
+        onChange={(e) => selectUpstream(e.target.value)}
+        options={[
+          { value: "", label: "選択してください" },
+          ...schedules.map((schedule) => ({
+            value: occurrenceKey(schedule),
+            label: `${schedule.title || "無題のスケジュール"} (${schedule.tool})`,
+          })),
+        ]}
+        disabled={disabled}
+      />
+      <Select
+        value={trigger.status}
+        onChange={(e) => onChange({ ...trigger, status: e.target.value as ChainTriggerStatus })}
+        options={(Object.keys(CHAIN_STATUS_LABELS) as ChainTriggerStatus[]).map((status) => ({
+          value: status,
+          label: CHAIN_STATUS_LABELS[status],
+        }))}
+        disabled={disabled}
+      />
+    </div>
+  );
+}
+
+// Schedule IDs are only unique per tool
+const occurrenceKey = (schedule: RegisteredSchedule) => `${schedule.tool}:${schedule.schedule_id}`;
+
+const getChainTrigger = (schedule: RegisteredSchedule, schedules: RegisteredSchedule[]): ChainTrigger => ({
+  // Chains saved before the upstream's tool was recorded take the first schedule with that ID
+  tool:
+    schedule.after_tool ??
+    schedules.find((other) => other.schedule_id === schedule.after_schedule_id)?.tool ??
+    "",
+  scheduleId: schedule.after_schedule_id ?? "",
+  status: schedule.after_status ?? "success",
+});
+
+const getMonthlyRule = (schedule: RegisteredSchedule): MonthlyRule => {
+  if (schedule.month_day === -1) return { ...DEFAULT_MONTHLY_RULE, mode: "last" };
+  if (schedule.month_day) return { ...DEFAULT_MONTHLY_RULE, mode: "day", day: schedule.month_day };
+  if (schedule.month_week) {
+    return {
+      ...DEFAULT_MONTHLY_RULE,
+      mode: "weekday",
+      week: schedule.month_week,
+      weekday: schedule.weekdays?.[0] ?? 1,
+    };
+  }
+  return DEFAULT_MONTHLY_RULE;
+};
+
+/** Parameters for register_schedule / update_schedule derived from a monthly rule */
+const monthlyRuleParams = (rule: MonthlyRule) => ({
+  monthDay: rule.mode === "day" ? rule.day : rule.mode === "last" ? -1 : undefined,
+  monthWeek: rule.mode === "weekday" ? rule.week : undefined,
+  weekdays: rule.mode === "weekday" ? [rule.weekday] : undefined,
+});
+
+const formatMonthlyRule = (schedule: RegisteredSchedule) => {
+  const rule = getMonthlyRule(schedule);
+  if (rule.mode === "last") return "月末";
+  if (rule.mode === "weekday") {
+    return `${MONTH_WEEK_LABELS[rule.week] ?? ""}${WEEKDAY_LABELS[rule.weekday]}曜日`;
+  }
+  return `${rule.day}日`;
+};
+
+const formatScheduleLimits = (schedule: RegisteredSchedule) => {
+  const limits: string[] = [];
+  if (schedule.end_date) limits.push(`${schedule.end_date} まで`);
+  if (schedule.max_runs) limits.push(`最大${schedule.max_runs}回`);
+  return limits.join(" ・ ");
+};
+
+const INTERVAL_UNIT_LABELS: Record<IntervalUnit, string> = {
+  days: "日",
+  hours: "時間",
+  minutes: "分",
+};
+
+/** Minutes within which runs of the same tool or directory are reported as conflicts */
+const DEFAULT_CONFLICT_WINDOW = 5;
+
+/** How many upcoming runs to show per schedule */
+const NEXT_OCCURRENCE_COUNT = 3;
+
+const isSubDailyInterval = (schedule: RegisteredSchedule) =>
+  schedule.schedule_type === "interval" &&
+  (schedule.interval_unit === "hours" || schedule.interval_unit === "minutes");
+
+const LOCAL_TIMEZONE = Intl.DateTimeFormat().resolvedOptions().timeZone;
+
+const COMMON_TIMEZONES = [
+  "Asia/Tokyo",
+  "Asia/Singapore",
+  "Asia/Kolkata",
+  "Europe/London",
+  "Europe/Berlin",
+  "America/New_York",
+  "America/Chicago",
+  "America/Los_Angeles",
+  "UTC",
+];
+
 const getTodayDateString = () => {
   const date = new Date();
   const year = date.getFullYear();
@@ -35,12 +390,26 @@ export function ScheduleManager({
 }: ScheduleManagerProps) {
   const [isRegistering, setIsRegistering] = useState(false);
   const [deletingScheduleId, setDeletingScheduleId] = useState<string | null>(null);
+  const [togglingScheduleId, setTogglingScheduleId] = useState<string | null>(null);
   const [message, setMessage] = useState("");
   
   const [scheduleType, setScheduleType] = useState<ScheduleType>('daily');
   const [intervalValue, setIntervalValue] = useState<number>(3);
+  const [intervalUnit, setIntervalUnit] = useState<IntervalUnit>("days");
+  const [activeStart, setActiveStart] = useState<string>("");
+  const [activeEnd, setActiveEnd] = useState<string>("");
   const [startDate, setStartDate] = useState<string>(getTodayDateString());
-  const [scheduleTime, setScheduleTime] = useState<string>(executionTime);
+  const [weekdays, setWeekdays] = useState<number[]>([new Date().getDay()]);
+  const [monthlyRule, setMonthlyRule] = useState<MonthlyRule>(DEFAULT_MONTHLY_RULE);
+  const [cronExpression, setCronExpression] = useState<string>("");
+  const [chainTrigger, setChainTrigger] = useState<ChainTrigger>(DEFAULT_CHAIN_TRIGGER);
+  const [endDate, setEndDate] = useState<string>("");
+  const [maxRuns, setMaxRuns] = useState<string>("");
+  const [timezone, setTimezone] = useState<string>("");
+  const [blackoutIds, setBlackoutIds] = useState<string[]>([]);
+  const [jitterMinutes, setJitterMinutes] = useState<string>("");
+  const [conflictWindow, setConflictWindow] = useState<string>(String(DEFAULT_CONFLICT_WINDOW));
+  const [scheduleTimes, setScheduleTimes] = useState<string[]>([executionTime]);
   const [scheduleTitle, setScheduleTitle] = useState<string>("");
   const [scheduleCommand, setScheduleCommand] = useState<string>("");
   const [scheduleTargetDirectory, setScheduleTargetDirectory] = useState<string>(targetDirectory);
@@ -48,28 +417,97 @@ export function ScheduleManager({
   const [editTitle, setEditTitle] = useState<string>("");
   const [editScheduleType, setEditScheduleType] = useState<ScheduleType>("daily");
   const [editIntervalValue, setEditIntervalValue] = useState<number>(3);
+  const [editIntervalUnit, setEditIntervalUnit] = useState<IntervalUnit>("days");
+  const [editActiveStart, setEditActiveStart] = useState<string>("");
+  const [editActiveEnd, setEditActiveEnd] = useState<string>("");
   const [editStartDate, setEditStartDate] = useState<string>(getTodayDateString());
-  const [editScheduleTime, setEditScheduleTime] = useState<string>(executionTime);
+  const [editWeekdays, setEditWeekdays] = useState<number[]>([new Date().getDay()]);
+  const [editMonthlyRule, setEditMonthlyRule] = useState<MonthlyRule>(DEFAULT_MONTHLY_RULE);
+  const [editCronExpression, setEditCronExpression] = useState<string>("");
+  const [editChainTrigger, setEditChainTrigger] = useState<ChainTrigger>(DEFAULT_CHAIN_TRIGGER);
+  const [allSchedules, setAllSchedules] = useState<RegisteredSchedule[]>([]);
+  const [editEndDate, setEditEndDate] = useState<string>("");
+  const [editMaxRuns, setEditMaxRuns] = useState<string>("");
+  const [editTimezone, setEditTimezone] = useState<string>("");
+  const [editBlackoutIds, setEditBlackoutIds] = useState<string[]>([]);
+  const [editJitterMinutes, setEditJitterMinutes] = useState<string>("");
+  const [blackoutCalendars, setBlackoutCalendars] = useState<BlackoutCalendar[]>([]);
+  const [nextOccurrences, setNextOccurrences] = useState<Record<string, string[]>>({});
+  const [editScheduleTimes, setEditScheduleTimes] = useState<string[]>([executionTime]);
   const [editScheduleCommand, setEditScheduleCommand] = useState<string>("");
   const [editTargetDirectory, setEditTargetDirectory] = useState<string>(targetDirectory);
   const [isUpdating, setIsUpdating] = useState(false);
   const [historyScheduleId, setHistoryScheduleId] = useState<string>("");
   const [historyEntries, setHistoryEntries] = useState<ScheduleHistoryEntry[]>([]);
   const [isHistoryLoading, setIsHistoryLoading] = useState(false);
+  const [drifts, setDrifts] = useState<ScheduleDrift[] | null>(null);
+  const [isReconciling, setIsReconciling] = useState(false);
+
+  const isSubDaily = scheduleType === "interval" && intervalUnit !== "days";
+  const isEditSubDaily = editScheduleType === "interval" && editIntervalUnit !== "days";
 
   const resetNewScheduleForm = () => {
     setScheduleTitle("");
     setScheduleCommand("");
     setScheduleType("daily");
     setIntervalValue(3);
+    setIntervalUnit("days");
+    setActiveStart("");
+    setActiveEnd("");
     setStartDate(getTodayDateString());
-    setScheduleTime(executionTime);
+    setWeekdays([new Date().getDay()]);
+    setMonthlyRule(DEFAULT_MONTHLY_RULE);
+    setCronExpression("");
+    setChainTrigger(DEFAULT_CHAIN_TRIGGER);
+    setEndDate("");
+    setMaxRuns("");
+    setTimezone("");
+    setBlackoutIds([]);
+    setJitterMinutes("");
+    setScheduleTimes([executionTime]);
     setScheduleTargetDirectory(targetDirectory);
   };
 
+  const loadBlackoutCalendars = () => {
+    invoke<BlackoutCalendar[]>("get_blackout_calendars")
+      .then(setBlackoutCalendars)
+      .catch(() => setBlackoutCalendars([]));
+  };
+
+  useEffect(() => {
+    loadBlackoutCalendars();
+  }, []);
+
+  // Chains may follow a schedule of another tool, so offer every registered schedule
+  useEffect(() => {
+    invoke<RegisteredSchedule[]>("get_registered_schedules")
+      .then(setAllSchedules)
+      .catch(() => setAllSchedules([]));
+  }, [registeredSchedules]);
+
+  useEffect(() => {
+    let cancelled = false;
+    Promise.all(
+      registeredSchedules.map((schedule) =>
+        invoke<string[]>("get_next_occurrences", {
+          tool: schedule.tool,
+          scheduleId: schedule.schedule_id,
+          count: NEXT_OCCURRENCE_COUNT,
+        })
+          .then((times) => [occurrenceKey(schedule), times] as const)
+          .catch(() => [occurrenceKey(schedule), [] as string[]] as const)
+      )
+    ).then((entries) => {
+      if (!cancelled) setNextOccurrences(Object.fromEntries(entries));
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [registeredSchedules, blackoutCalendars]);
+
   useEffect(() => {
-    setScheduleTime(executionTime);
-    setEditScheduleTime(executionTime);
+    setScheduleTimes([executionTime]);
+    setEditScheduleTimes([executionTime]);
   }, [executionTime]);
 
   useEffect(() => {
@@ -99,9 +537,11 @@ export function ScheduleManager({
 
   const sortedSchedules = useMemo(() => {
     return [...registeredSchedules].sort((a, b) => {
-      const timeOrder = a.execution_time.localeCompare(b.execution_time);
+      const timeOrder = (a.execution_time[0] ?? "").localeCompare(b.execution_time[0] ?? "");
       if (timeOrder !== 0) return timeOrder;
-      return a.schedule_type.localeCompare(b.schedule_type);
+      const typeOrder = a.schedule_type.localeCompare(b.schedule_type);
+      if (typeOrder !== 0) return typeOrder;
+      return a.created_at.localeCompare(b.created_at);
     });
   }, [registeredSchedules]);
 
@@ -128,6 +568,7 @@ export function ScheduleManager({
     let cancelled = false;
     setIsHistoryLoading(true);
     invoke<ScheduleHistoryEntry[]>("get_schedule_history", {
+      tool,
       scheduleId: historyScheduleId,
     })
       .then((entries) => {
@@ -149,7 +590,7 @@ export function ScheduleManager({
     return () => {
       cancelled = true;
     };
-  }, [historyScheduleId]);
+  }, [tool, historyScheduleId]);
 
   const handleRegisterSchedule = async () => {
     if (!scheduleTargetDirectory.trim()) {
@@ -167,13 +608,28 @@ export function ScheduleManager({
     try {
       const result = await invoke<ScheduleResult>("register_schedule", {
         tool,
-        executionTime: scheduleTime,
+        executionTime: scheduleTimes,
         targetDirectory: scheduleTargetDirectory,
         commandArgs: scheduleCommand,
         title: scheduleTitle.trim() || "無題のスケジュール",
         scheduleType,
         intervalValue: scheduleType === 'interval' ? intervalValue : undefined,
-        startDate: scheduleType !== 'daily' ? startDate : undefined,
+        intervalUnit: scheduleType === 'interval' ? intervalUnit : undefined,
+        activeStart: isSubDaily ? activeStart || undefined : undefined,
+        activeEnd: isSubDaily ? activeEnd || undefined : undefined,
+        startDate: scheduleType === 'interval' && !isSubDaily ? startDate : undefined,
+        weekdays: scheduleType === 'weekly' ? weekdays : undefined,
+        ...(scheduleType === 'monthly' ? monthlyRuleParams(monthlyRule) : {}),
+        cronExpression: scheduleType === 'cron' ? cronExpression.trim() : undefined,
+        afterTool: scheduleType === 'chain' ? chainTrigger.tool : undefined,
+        afterScheduleId: scheduleType === 'chain' ? chainTrigger.scheduleId : undefined,
+        afterStatus: scheduleType === 'chain' ? chainTrigger.status : undefined,
+        endDate: endDate || undefined,
+        maxRuns: maxRuns ? parseInt(maxRuns) : undefined,
+        timezone: timezone.trim() || undefined,
+        blackoutCalendars: blackoutIds.length > 0 ? blackoutIds : undefined,
+        jitterMinutes: jitterMinutes ? parseInt(jitterMinutes) : undefined,
+        conflictWindowMinutes: conflictWindow ? parseInt(conflictWindow) : undefined,
       });
 
       setMessage(result.message);
@@ -195,10 +651,28 @@ export function ScheduleManager({
   const startEditing = (schedule: RegisteredSchedule) => {
     setEditingScheduleId(schedule.schedule_id);
     setEditTitle(schedule.title);
-    setEditScheduleTime(schedule.execution_time);
+    setEditScheduleTimes(
+      schedule.execution_time.length > 0 ? schedule.execution_time : [executionTime]
+    );
     setEditScheduleType(schedule.schedule_type);
     setEditIntervalValue(schedule.interval_value ?? 3);
+    setEditIntervalUnit(schedule.interval_unit ?? "days");
+    setEditActiveStart(schedule.active_start ?? "");
+    setEditActiveEnd(schedule.active_end ?? "");
     setEditStartDate(schedule.start_date ?? getTodayDateString());
+    setEditWeekdays(
+      schedule.schedule_type === "weekly" ? getScheduleWeekdays(schedule) : [new Date().getDay()]
+    );
+    setEditMonthlyRule(
+      schedule.schedule_type === "monthly" ? getMonthlyRule(schedule) : DEFAULT_MONTHLY_RULE
+    );
+    setEditCronExpression(schedule.cron_expression ?? "");
+    setEditChainTrigger(getChainTrigger(schedule, allSchedules));
+    setEditEndDate(schedule.end_date ?? "");
+    setEditMaxRuns(schedule.max_runs ? String(schedule.max_runs) : "");
+    setEditTimezone(schedule.timezone ?? "");
+    setEditBlackoutIds(schedule.blackout_calendars ?? []);
+    setEditJitterMinutes(schedule.jitter_minutes ? String(schedule.jitter_minutes) : "");
     setEditScheduleCommand(schedule.command_args ?? "");
     setEditTargetDirectory(schedule.target_directory ?? targetDirectory);
   };
@@ -225,13 +699,28 @@ export function ScheduleManager({
       const result = await invoke<ScheduleResult>("update_schedule", {
         tool,
         scheduleId: editingScheduleId,
-        executionTime: editScheduleTime,
+        executionTime: editScheduleTimes,
         targetDirectory: editTargetDirectory,
         commandArgs: editScheduleCommand,
         title: editTitle.trim() || "無題のスケジュール",
         scheduleType: editScheduleType,
         intervalValue: editScheduleType === "interval" ? editIntervalValue : undefined,
-        startDate: editScheduleType !== "daily" ? editStartDate : undefined,
+        intervalUnit: editScheduleType === "interval" ? editIntervalUnit : undefined,
+        activeStart: isEditSubDaily ? editActiveStart || undefined : undefined,
+        activeEnd: isEditSubDaily ? editActiveEnd || undefined : undefined,
+        startDate: editScheduleType === "interval" && !isEditSubDaily ? editStartDate : undefined,
+        weekdays: editScheduleType === "weekly" ? editWeekdays : undefined,
+        ...(editScheduleType === "monthly" ? monthlyRuleParams(editMonthlyRule) : {}),
+        cronExpression: editScheduleType === "cron" ? editCronExpression.trim() : undefined,
+        afterTool: editScheduleType === "chain" ? editChainTrigger.tool : undefined,
+        afterScheduleId: editScheduleType === "chain" ? editChainTrigger.scheduleId : undefined,
+        afterStatus: editScheduleType === "chain" ? editChainTrigger.status : undefined,
+        endDate: editEndDate || undefined,
+        maxRuns: editMaxRuns ? parseInt(editMaxRuns) : undefined,
+        timezone: editTimezone.trim() || undefined,
+        blackoutCalendars: editBlackoutIds.length > 0 ? editBlackoutIds : undefined,
+        jitterMinutes: editJitterMinutes ? parseInt(editJitterMinutes) : undefined,
+        conflictWindowMinutes: conflictWindow ? parseInt(conflictWindow) : undefined,
       });
 
       setMessage(result.message);
@@ -275,15 +764,83 @@ export function ScheduleManager({
     }
   };
 
+  const handleReconcileSchedules = async (repair: boolean) => {
+    setIsReconciling(true);
+    setMessage(repair ? "ジョブを修復中..." : "ジョブを点検中...");
+
+    try {
+      const result = await invoke<ScheduleDrift[]>("reconcile_schedules", { repair });
+      setDrifts(result);
+      if (result.length === 0) {
+        setMessage("点検完了: すべてのジョブが登録内容と一致しています");
+      } else if (!repair) {
+        setMessage(`点検完了: ${result.length}件の不一致が見つかりました`);
+      } else if (result.every((drift) => drift.repaired)) {
+        setMessage(`修復成功: ${result.length}件の不一致を修復しました`);
+      } else {
+        setMessage("エラー: 一部の不一致を修復できませんでした");
+      }
+      if (repair) {
+        onScheduleRegister(true);
+      }
+    } catch (error) {
+      setMessage(`エラー: ${error}`);
+    } finally {
+      setIsReconciling(false);
+    }
+  };
+
+  const handleToggleSchedule = async (schedule: RegisteredSchedule) => {
+    const pausing = schedule.enabled;
+    setTogglingScheduleId(schedule.schedule_id);
+    setMessage(pausing ? "スケジュール一時停止中..." : "スケジュール再開中...");
+
+    try {
+      const result = await invoke<ScheduleResult>(pausing ? "pause_schedule" : "resume_schedule", {
+        tool,
+        scheduleId: schedule.schedule_id,
+      });
+
+      setMessage(result.message);
+      onScheduleRegister(result.success);
+    } catch (error) {
+      const errorMsg = `エラー: ${error}`;
+      setMessage(errorMsg);
+      onScheduleRegister(false);
+    } finally {
+      setTogglingScheduleId(null);
+    }
+  };
+
   const getScheduleDescription = (schedule: RegisteredSchedule) => {
-    const time = schedule.execution_time;
+    const zone = schedule.timezone ? ` (${schedule.timezone})` : "";
+    const time = schedule.execution_time.join(", ") + zone;
     switch (schedule.schedule_type) {
       case 'daily':
         return `毎日 ${time}`;
       case 'weekly':
-        return `毎週 (${schedule.start_date} 開始) ${time}`;
+        return `毎週 ${formatWeekdays(getScheduleWeekdays(schedule))} ${time}`;
+      case 'monthly':
+        return `毎月 ${formatMonthlyRule(schedule)} ${time}`;
       case 'interval':
+        if (isSubDailyInterval(schedule)) {
+          const unit = INTERVAL_UNIT_LABELS[schedule.interval_unit ?? "days"];
+          const window =
+            schedule.active_start && schedule.active_end
+              ? ` (${schedule.active_start}〜${schedule.active_end})`
+              : "";
+          return `${schedule.interval_value}${unit}ごと${window}`;
+        }
         return `${schedule.interval_value}日ごと (${schedule.start_date} 開始) ${time}`;
+      case 'cron':
+        return `cron: ${schedule.cron_expression ?? ""}${zone}`;
+      case 'chain': {
+        const trigger = getChainTrigger(schedule, allSchedules);
+        const upstream = allSchedules.find(
+          (other) => other.tool === trigger.tool && other.schedule_id === trigger.scheduleId
+        );
+        return `「${upstream?.title ?? trigger.scheduleId}」が${CHAIN_STATUS_LABELS[trigger.status]}実行`;
+      }
       default:
         return `毎日 ${time}`;
     }
@@ -297,6 +854,18 @@ export function ScheduleManager({
         return "失敗";
       case "skipped":
         return "スキップ";
+      case "started":
+        return "開始";
+      case "skipped-blackout":
+        return "スキップ（除外日）";
+      case "skipped-expired":
+        return "スキップ（期限切れ）";
+      case "skipped-running":
+        return "スキップ（実行中）";
+      case "cancelled":
+        return "中止";
+      case "expired":
+        return "終了（登録解除）";
       case "wake-triggered":
         return "起床成功";
       case "wake-missed":
@@ -309,6 +878,8 @@ export function ScheduleManager({
         return "補填失敗";
       case "catchup-skipped-running":
         return "補填スキップ（実行中）";
+      case "catchup-cancelled":
+        return "補填中止";
       default:
         return status;
     }
@@ -322,103 +893,110 @@ export function ScheduleManager({
     return parsed.toLocaleString();
   };
 
-  const parseScheduleTime = (time: string) => {
-    const [hoursText, minutesText] = time.split(":");
-    const hours = Number(hoursText);
-    const minutes = Number(minutesText);
-    if (!Number.isInteger(hours) || !Number.isInteger(minutes)) {
-      return null;
-    }
-    if (hours < 0 || hours > 23 || minutes < 0 || minutes > 59) {
-      return null;
-    }
-    return { hours, minutes };
+  const MODIFIER_LABELS: Record<NonNullable<RegisteredSchedule["last_modified_by"]>, string> = {
+    ui: "UI",
+    import: "インポート",
   };
 
-  const toDateAtTime = (date: Date, hours: number, minutes: number) =>
-    new Date(date.getFullYear(), date.getMonth(), date.getDate(), hours, minutes, 0, 0);
-
-  const getNextExecution = (schedule: RegisteredSchedule) => {
-    const time = parseScheduleTime(schedule.execution_time);
-    if (!time) return null;
-
-    const now = new Date();
-    const todayAtTime = toDateAtTime(now, time.hours, time.minutes);
-
-    if (schedule.schedule_type === "daily") {
-      if (todayAtTime > now) return todayAtTime;
-      return new Date(todayAtTime.getTime() + 24 * 60 * 60 * 1000);
-    }
-
-    if (schedule.schedule_type === "weekly") {
-      if (!schedule.start_date) return null;
-      const startDate = new Date(`${schedule.start_date}T00:00:00`);
-      if (Number.isNaN(startDate.getTime())) return null;
-
-      const targetDay = startDate.getDay();
-      const today = now.getDay();
-      let diff = (targetDay - today + 7) % 7;
-      const candidate = new Date(todayAtTime);
-      if (diff === 0 && candidate <= now) {
-        diff = 7;
-      }
-      candidate.setDate(candidate.getDate() + diff);
-      return candidate;
-    }
-
-    if (schedule.schedule_type === "interval") {
-      if (!schedule.start_date || !schedule.interval_value) return null;
-      const startMidnight = new Date(`${schedule.start_date}T00:00:00`);
-      if (Number.isNaN(startMidnight.getTime())) return null;
-
-      const intervalDays = schedule.interval_value;
-      const nowMidnight = new Date(now.getFullYear(), now.getMonth(), now.getDate());
-      const daysSinceStart = Math.floor(
-        (nowMidnight.getTime() - startMidnight.getTime()) / (24 * 60 * 60 * 1000)
-      );
-
-      let nextDate = new Date(startMidnight);
-      if (daysSinceStart >= 0) {
-        const offset = daysSinceStart % intervalDays;
-        const daysUntil = offset === 0 ? 0 : intervalDays - offset;
-        nextDate = new Date(nowMidnight);
-        nextDate.setDate(nextDate.getDate() + daysUntil);
-      }
-
-      let candidate = toDateAtTime(nextDate, time.hours, time.minutes);
-      if (candidate <= now) {
-        nextDate.setDate(nextDate.getDate() + intervalDays);
-        candidate = toDateAtTime(nextDate, time.hours, time.minutes);
-      }
-      return candidate;
+  const formatScheduleStamps = (schedule: RegisteredSchedule) => {
+    const created = `登録: ${formatHistoryTimestamp(schedule.created_at)}`;
+    if (!schedule.updated_at) {
+      return created;
     }
+    const modifier = schedule.last_modified_by ? `（${MODIFIER_LABELS[schedule.last_modified_by]}）` : "";
+    return `${created} ・ 更新: ${formatHistoryTimestamp(schedule.updated_at)}${modifier}`;
+  };
 
-    return null;
+  // Worded for every backend: the definition is the plist, service unit or env file, and
+  // the trigger is the LaunchAgents plist, timer unit or crontab lines
+  const DRIFT_LABELS: Record<ScheduleDriftKind, string> = {
+    "missing-config": "ジョブの定義ファイルがありません",
+    "config-differs": "ジョブの定義ファイルが登録内容と異なります",
+    "missing-agent": "ジョブのトリガーがありません",
+    "agent-differs": "ジョブのトリガーが登録内容と異なります",
+    "install-differs": "アプリの場所が登録時から変わっています",
+    "not-loaded": "スケジューラーに読み込まれていません",
+    "paused-but-loaded": "一時停止中なのにスケジューラーに残っています",
+    "orphan-file": "削除済みスケジュールのファイルが残っています",
+    "orphan-job": "削除済みスケジュールのジョブがスケジューラーに残っています",
   };
 
+  // Times come from the backend's occurrence logic, so interval, monthly, cron and
+  // zoned schedules all match what launchd and check-due will actually do
   const formatNextExecution = (schedule: RegisteredSchedule) => {
-    const next = getNextExecution(schedule);
-    if (!next) return "不明";
-    return next.toLocaleString("ja-JP", {
+    const next = nextOccurrences[occurrenceKey(schedule)]?.[0];
+    if (!next) return "なし";
+    return new Date(next).toLocaleString("ja-JP", {
       dateStyle: "medium",
       timeStyle: "short",
     });
   };
 
+  const formatFollowingExecutions = (schedule: RegisteredSchedule) =>
+    (nextOccurrences[occurrenceKey(schedule)] ?? [])
+      .slice(1)
+      .map((time) =>
+        new Date(time).toLocaleString("ja-JP", {
+          month: "numeric",
+          day: "numeric",
+          hour: "2-digit",
+          minute: "2-digit",
+        })
+      )
+      .join("、");
+
   return (
     <div className="space-y-4">
+      <datalist id="schedule-timezones">
+        {COMMON_TIMEZONES.map((zone) => (
+          <option key={zone} value={zone} />
+        ))}
+      </datalist>
       <div className="space-y-3">
         <div className="flex items-center justify-between">
           <p className="text-sm font-medium text-gray-700 dark:text-gray-300">
             登録済みスケジュール
           </p>
-          {registeredSchedules.length > 0 && (
-            <span className="text-xs text-gray-500">
-              {registeredSchedules.length}件
-            </span>
-          )}
+          <div className="flex items-center gap-2">
+            {registeredSchedules.length > 0 && (
+              <span className="text-xs text-gray-500">
+                {registeredSchedules.length}件
+              </span>
+            )}
+            <Button
+              variant="secondary"
+              onClick={() => handleReconcileSchedules(false)}
+              disabled={isReconciling}
+              isLoading={isReconciling}
+            >
+              ジョブを点検
+            </Button>
+          </div>
         </div>
 
+        {drifts && drifts.length > 0 && (
+          <div className="space-y-2 rounded-md border border-yellow-200 dark:border-yellow-800 bg-yellow-50 dark:bg-yellow-900/20 p-3">
+            <ul className="space-y-1 text-xs text-gray-700 dark:text-gray-300">
+              {drifts.map((drift) => (
+                <li key={`${drift.tool}-${drift.schedule_id}-${drift.kind}`}>
+                  {drift.tool} / {drift.schedule_id}: {DRIFT_LABELS[drift.kind]}
+                  {drift.repaired && "（修復済み）"}
+                  {drift.error && `（修復失敗: ${drift.error}）`}
+                </li>
+              ))}
+            </ul>
+            {drifts.some((drift) => !drift.repaired) && (
+              <Button
+                variant="secondary"
+                onClick={() => handleReconcileSchedules(true)}
+                disabled={isReconciling}
+              >
+                修復する
+              </Button>
+            )}
+          </div>
+        )}
+
         {sortedSchedules.length === 0 ? (
           <div className="rounded-md border border-dashed border-gray-200 dark:border-gray-700 p-4 text-sm text-gray-500 dark:text-gray-400">
             まだスケジュールがありません。下のフォームから追加できます。
@@ -433,22 +1011,49 @@ export function ScheduleManager({
                 <div className="flex-1 space-y-1">
                   <p className="text-sm font-semibold text-gray-800 dark:text-gray-100">
                     {schedule.title || "無題のスケジュール"}
+                    {!schedule.enabled && (
+                      <Badge variant="warning" size="sm" className="ml-2">
+                        一時停止中
+                      </Badge>
+                    )}
                   </p>
                   <p className="text-xs text-gray-500">
                     {getScheduleDescription(schedule)}
                     {" ・ "}
-                    {schedule.schedule_type === "interval" && schedule.start_date
+                    {isSubDailyInterval(schedule)
+                      ? "繰り返し: 指定間隔"
+                      : schedule.schedule_type === "interval" && schedule.start_date
                       ? `開始日: ${schedule.start_date}`
-                      : schedule.schedule_type === "weekly" && schedule.start_date
-                        ? `曜日基準: ${schedule.start_date}`
-                        : "繰り返し: 毎日"}
+                      : schedule.schedule_type === "weekly"
+                        ? `曜日: ${formatWeekdays(getScheduleWeekdays(schedule))}`
+                        : schedule.schedule_type === "monthly"
+                          ? `毎月: ${formatMonthlyRule(schedule)}`
+                        : schedule.schedule_type === "cron"
+                          ? "繰り返し: cron式"
+                        : schedule.schedule_type === "chain"
+                          ? "繰り返し: 先行スケジュールの完了ごと"
+                          : "繰り返し: 毎日"}
                   </p>
                   <p className="text-xs text-gray-500">
                     次回実行: {formatNextExecution(schedule)}
+                    {schedule.jitter_minutes ? `（0〜${schedule.jitter_minutes}分遅れて開始）` : ""}
                   </p>
+                  {formatFollowingExecutions(schedule) && (
+                    <p className="text-xs text-gray-500">
+                      以降: {formatFollowingExecutions(schedule)}
+                    </p>
+                  )}
+                  {(schedule.end_date || schedule.max_runs) && (
+                    <p className="text-xs text-gray-500">
+                      終了条件: {formatScheduleLimits(schedule)}
+                    </p>
+                  )}
                   <p className="text-xs text-gray-500 break-all">
                     実行対象: {schedule.target_directory || "未指定"}
                   </p>
+                  <p className="text-xs text-gray-500">
+                    {formatScheduleStamps(schedule)}
+                  </p>
                 </div>
                 <div className="flex items-center gap-2">
                   <Button
@@ -458,6 +1063,14 @@ export function ScheduleManager({
                   >
                     編集
                   </Button>
+                  <Button
+                    variant="secondary"
+                    onClick={() => handleToggleSchedule(schedule)}
+                    disabled={isRunning || togglingScheduleId === schedule.schedule_id}
+                    isLoading={togglingScheduleId === schedule.schedule_id}
+                  >
+                    {schedule.enabled ? "一時停止" : "再開"}
+                  </Button>
                   <Button
                     variant="danger"
                     onClick={() => handleUnregisterSchedule(schedule.schedule_id)}
@@ -483,7 +1096,7 @@ export function ScheduleManager({
           onChange={(e) => setHistoryScheduleId(e.target.value)}
           options={sortedSchedules.map((schedule) => ({
             value: schedule.schedule_id,
-            label: `${schedule.title || "無題のスケジュール"} (${schedule.execution_time})`,
+            label: `${schedule.title || "無題のスケジュール"} (${schedule.execution_time.join(", ")})`,
           }))}
           disabled={sortedSchedules.length === 0 || isRunning}
         />
@@ -579,11 +1192,12 @@ export function ScheduleManager({
               <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
                 実行時刻
               </label>
-              <Input
-                type="time"
-                value={editScheduleTime}
-                onChange={(e) => setEditScheduleTime(e.target.value)}
-                disabled={isRunning}
+              <ExecutionTimesInput
+                times={editScheduleTimes}
+                onChange={setEditScheduleTimes}
+                disabled={
+                  isRunning || editScheduleType === "cron" || editScheduleType === "chain" || isEditSubDaily
+                }
               />
             </div>
 
@@ -614,6 +1228,17 @@ export function ScheduleManager({
                   />
                   <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">毎週</span>
                 </label>
+                <label className="inline-flex items-center">
+                  <input
+                    type="radio"
+                    className="form-radio text-primary-DEFAULT"
+                    name={`editScheduleType-${tool}`}
+                    value="monthly"
+                    checked={editScheduleType === "monthly"}
+                    onChange={() => setEditScheduleType("monthly")}
+                  />
+                  <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">毎月</span>
+                </label>
                 <label className="inline-flex items-center">
                   <input
                     type="radio"
@@ -625,6 +1250,28 @@ export function ScheduleManager({
                   />
                   <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">指定間隔</span>
                 </label>
+                <label className="inline-flex items-center">
+                  <input
+                    type="radio"
+                    className="form-radio text-primary-DEFAULT"
+                    name={`editScheduleType-${tool}`}
+                    value="cron"
+                    checked={editScheduleType === "cron"}
+                    onChange={() => setEditScheduleType("cron")}
+                  />
+                  <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">cron式</span>
+                </label>
+                <label className="inline-flex items-center">
+                  <input
+                    type="radio"
+                    className="form-radio text-primary-DEFAULT"
+                    name={`editScheduleType-${tool}`}
+                    value="chain"
+                    checked={editScheduleType === "chain"}
+                    onChange={() => setEditScheduleType("chain")}
+                  />
+                  <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">他の完了後</span>
+                </label>
               </div>
             </div>
           </div>
@@ -633,19 +1280,98 @@ export function ScheduleManager({
             {editScheduleType === "interval" && (
               <div>
                 <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
-                  実行間隔（日）
+                  実行間隔
+                </label>
+                <div className="flex gap-2">
+                  <Input
+                    type="number"
+                    min={editIntervalUnit === "days" ? 2 : 1}
+                    value={editIntervalValue}
+                    onChange={(e) =>
+                      setEditIntervalValue(parseInt(e.target.value) || (editIntervalUnit === "days" ? 2 : 1))
+                    }
+                    className="w-full"
+                  />
+                  <Select
+                    value={editIntervalUnit}
+                    onChange={(e) => setEditIntervalUnit(e.target.value as IntervalUnit)}
+                    options={[
+                      { value: "days", label: "日" },
+                      { value: "hours", label: "時間" },
+                      { value: "minutes", label: "分" },
+                    ]}
+                  />
+                </div>
+              </div>
+            )}
+
+            {isEditSubDaily && (
+              <div>
+                <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                  実行時間帯（任意）
+                </label>
+                <div className="flex items-center gap-2">
+                  <Input
+                    type="time"
+                    value={editActiveStart}
+                    onChange={(e) => setEditActiveStart(e.target.value)}
+                  />
+                  <span className="text-sm text-gray-500">〜</span>
+                  <Input
+                    type="time"
+                    value={editActiveEnd}
+                    onChange={(e) => setEditActiveEnd(e.target.value)}
+                  />
+                </div>
+              </div>
+            )}
+
+            {editScheduleType === "cron" && (
+              <div className="md:col-span-2">
+                <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                  cron式（分 時 日 月 曜日）
                 </label>
                 <Input
-                  type="number"
-                  min={2}
-                  value={editIntervalValue}
-                  onChange={(e) => setEditIntervalValue(parseInt(e.target.value) || 2)}
+                  type="text"
+                  value={editCronExpression}
+                  onChange={(e) => setEditCronExpression(e.target.value)}
+                  placeholder="例: */30 9-18 * * 1-5"
                   className="w-full"
                 />
+                <p className="text-xs text-gray-500 mt-1">
+                  ※cron式を指定した場合、実行時刻の設定は使用されません
+                </p>
+              </div>
+            )}
+
+            {editScheduleType === "chain" && (
+              <div className="md:col-span-2">
+                <ChainTriggerInput
+                  trigger={editChainTrigger}
+                  schedules={allSchedules.filter(
+                    (schedule) => schedule.tool !== tool || schedule.schedule_id !== editingScheduleId
+                  )}
+                  onChange={setEditChainTrigger}
+                />
+              </div>
+            )}
+
+            {editScheduleType === "weekly" && (
+              <div className="md:col-span-2">
+                <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                  実行曜日
+                </label>
+                <WeekdaySelector weekdays={editWeekdays} onChange={setEditWeekdays} />
+              </div>
+            )}
+
+            {editScheduleType === "monthly" && (
+              <div className="md:col-span-2">
+                <MonthlyRuleInput rule={editMonthlyRule} onChange={setEditMonthlyRule} />
               </div>
             )}
 
-            {editScheduleType !== "daily" && (
+            {editScheduleType === "interval" && !isEditSubDaily && (
               <div>
                 <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
                   開始日（基準日）
@@ -657,12 +1383,76 @@ export function ScheduleManager({
                   className="w-full"
                 />
                 <p className="text-xs text-gray-500 mt-1">
-                  {editScheduleType === "weekly"
-                    ? "※指定した日付の曜日で毎週実行されます"
-                    : "※この日付を基準に実行判定を行います"}
+                  ※この日付を基準に実行判定を行います
                 </p>
               </div>
             )}
+
+            <div>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                終了日（任意）
+              </label>
+              <Input
+                type="date"
+                value={editEndDate}
+                onChange={(e) => setEditEndDate(e.target.value)}
+                className="w-full"
+              />
+            </div>
+
+            <div>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                最大実行回数（任意）
+              </label>
+              <Input
+                type="number"
+                min={1}
+                value={editMaxRuns}
+                onChange={(e) => setEditMaxRuns(e.target.value)}
+                placeholder="無制限"
+                className="w-full"
+              />
+            </div>
+
+            <div>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                タイムゾーン（任意）
+              </label>
+              <Input
+                type="text"
+                list="schedule-timezones"
+                value={editTimezone}
+                onChange={(e) => setEditTimezone(e.target.value)}
+                placeholder={LOCAL_TIMEZONE}
+                className="w-full"
+              />
+            </div>
+
+            <div>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                開始の揺らぎ（任意・分）
+              </label>
+              <Input
+                type="number"
+                min={0}
+                max={120}
+                value={editJitterMinutes}
+                onChange={(e) => setEditJitterMinutes(e.target.value)}
+                placeholder="0"
+                className="w-full"
+              />
+            </div>
+
+            <div className="md:col-span-2">
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                除外カレンダー
+              </label>
+              <BlackoutCalendarSelector
+                calendars={blackoutCalendars}
+                selected={editBlackoutIds}
+                onChange={setEditBlackoutIds}
+              />
+            </div>
           </div>
 
           <div className="flex gap-2">
@@ -741,11 +1531,10 @@ export function ScheduleManager({
             <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
               実行時刻
             </label>
-            <Input
-              type="time"
-              value={scheduleTime}
-              onChange={(e) => setScheduleTime(e.target.value)}
-              disabled={isRunning}
+            <ExecutionTimesInput
+              times={scheduleTimes}
+              onChange={setScheduleTimes}
+              disabled={isRunning || scheduleType === 'cron' || scheduleType === 'chain' || isSubDaily}
             />
             <p className="text-xs text-gray-500 mt-1">
               ※共通設定の時刻を初期値として反映します
@@ -779,6 +1568,17 @@ export function ScheduleManager({
                 />
                 <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">毎週</span>
               </label>
+              <label className="inline-flex items-center">
+                <input
+                  type="radio"
+                  className="form-radio text-primary-DEFAULT"
+                  name={`scheduleType-${tool}`}
+                  value="monthly"
+                  checked={scheduleType === 'monthly'}
+                  onChange={() => setScheduleType('monthly')}
+                />
+                <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">毎月</span>
+              </label>
               <label className="inline-flex items-center">
                 <input
                   type="radio"
@@ -790,6 +1590,28 @@ export function ScheduleManager({
                 />
                 <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">指定間隔</span>
               </label>
+              <label className="inline-flex items-center">
+                <input
+                  type="radio"
+                  className="form-radio text-primary-DEFAULT"
+                  name={`scheduleType-${tool}`}
+                  value="cron"
+                  checked={scheduleType === 'cron'}
+                  onChange={() => setScheduleType('cron')}
+                />
+                <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">cron式</span>
+              </label>
+              <label className="inline-flex items-center">
+                <input
+                  type="radio"
+                  className="form-radio text-primary-DEFAULT"
+                  name={`scheduleType-${tool}`}
+                  value="chain"
+                  checked={scheduleType === 'chain'}
+                  onChange={() => setScheduleType('chain')}
+                />
+                <span className="ml-2 text-sm text-gray-700 dark:text-gray-300">他の完了後</span>
+              </label>
             </div>
           </div>
         </div>
@@ -798,19 +1620,105 @@ export function ScheduleManager({
           {scheduleType === 'interval' && (
             <div>
               <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
-                実行間隔（日）
+                実行間隔
+              </label>
+              <div className="flex gap-2">
+                <Input
+                  type="number"
+                  min={intervalUnit === "days" ? 2 : 1}
+                  value={intervalValue}
+                  onChange={(e) =>
+                    setIntervalValue(parseInt(e.target.value) || (intervalUnit === "days" ? 2 : 1))
+                  }
+                  className="w-full"
+                />
+                <Select
+                  value={intervalUnit}
+                  onChange={(e) => setIntervalUnit(e.target.value as IntervalUnit)}
+                  options={[
+                    { value: "days", label: "日" },
+                    { value: "hours", label: "時間" },
+                    { value: "minutes", label: "分" },
+                  ]}
+                />
+              </div>
+            </div>
+          )}
+
+          {isSubDaily && (
+            <div>
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                実行時間帯（任意）
+              </label>
+              <div className="flex items-center gap-2">
+                <Input
+                  type="time"
+                  value={activeStart}
+                  onChange={(e) => setActiveStart(e.target.value)}
+                />
+                <span className="text-sm text-gray-500">〜</span>
+                <Input
+                  type="time"
+                  value={activeEnd}
+                  onChange={(e) => setActiveEnd(e.target.value)}
+                />
+              </div>
+              <p className="text-xs text-gray-500 mt-1">
+                ※時間/分単位の間隔では実行時刻の設定は使用されません
+              </p>
+            </div>
+          )}
+
+          {scheduleType === 'cron' && (
+            <div className="md:col-span-2">
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                cron式（分 時 日 月 曜日）
               </label>
               <Input
-                type="number"
-                min={2}
-                value={intervalValue}
-                onChange={(e) => setIntervalValue(parseInt(e.target.value) || 2)}
+                type="text"
+                value={cronExpression}
+                onChange={(e) => setCronExpression(e.target.value)}
+                placeholder="例: */30 9-18 * * 1-5"
                 className="w-full"
               />
+              <p className="text-xs text-gray-500 mt-1">
+                ※cron式を指定した場合、実行時刻の設定は使用されません
+              </p>
+            </div>
+          )}
+
+          {scheduleType === 'chain' && (
+            <div className="md:col-span-2">
+              <ChainTriggerInput
+                trigger={chainTrigger}
+                schedules={allSchedules}
+                onChange={setChainTrigger}
+              />
+              <p className="text-xs text-gray-500 mt-1">
+                ※先行スケジュールの実行が終わるとすぐに開始します（実行時刻の設定は使用されません）
+              </p>
+            </div>
+          )}
+
+          {scheduleType === 'weekly' && (
+            <div className="md:col-span-2">
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                実行曜日
+              </label>
+              <WeekdaySelector weekdays={weekdays} onChange={setWeekdays} />
+            </div>
+          )}
+
+          {scheduleType === 'monthly' && (
+            <div className="md:col-span-2">
+              <MonthlyRuleInput rule={monthlyRule} onChange={setMonthlyRule} />
+              <p className="text-xs text-gray-500 mt-1">
+                ※29日以降を指定した場合、その日がない月は実行されません（月末指定を推奨）
+              </p>
             </div>
           )}
 
-          {scheduleType !== 'daily' && (
+          {scheduleType === 'interval' && !isSubDaily && (
             <div>
               <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
                 開始日（基準日）
@@ -822,12 +1730,104 @@ export function ScheduleManager({
                 className="w-full"
               />
               <p className="text-xs text-gray-500 mt-1">
-                {scheduleType === 'weekly' 
-                  ? "※指定した日付の曜日で毎週実行されます" 
-                  : "※この日付を基準に実行判定を行います"}
+                ※この日付を基準に実行判定を行います
               </p>
             </div>
           )}
+
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+              終了日（任意）
+            </label>
+            <Input
+              type="date"
+              value={endDate}
+              onChange={(e) => setEndDate(e.target.value)}
+              className="w-full"
+            />
+          </div>
+
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+              最大実行回数（任意）
+            </label>
+            <Input
+              type="number"
+              min={1}
+              value={maxRuns}
+              onChange={(e) => setMaxRuns(e.target.value)}
+              placeholder="無制限"
+              className="w-full"
+            />
+            <p className="text-xs text-gray-500 mt-1">
+              ※終了日または回数に達すると自動で登録解除されます
+            </p>
+          </div>
+
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+              タイムゾーン（任意）
+            </label>
+            <Input
+              type="text"
+              list="schedule-timezones"
+              value={timezone}
+              onChange={(e) => setTimezone(e.target.value)}
+              placeholder={LOCAL_TIMEZONE}
+              className="w-full"
+            />
+            <p className="text-xs text-gray-500 mt-1">
+              ※指定すると実行時刻をそのタイムゾーンの時刻として扱います（空欄はこのMacの設定）
+            </p>
+          </div>
+
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+              開始の揺らぎ（任意・分）
+            </label>
+            <Input
+              type="number"
+              min={0}
+              max={120}
+              value={jitterMinutes}
+              onChange={(e) => setJitterMinutes(e.target.value)}
+              placeholder="0"
+              className="w-full"
+            />
+            <p className="text-xs text-gray-500 mt-1">
+              ※実行時刻から0〜指定分のあいだ、日ごとに決まったランダムな時間だけ遅らせて開始します（同時刻のスケジュールの集中を防ぎます）
+            </p>
+          </div>
+
+          <div>
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+              近接チェックの幅（分）
+            </label>
+            <Input
+              type="number"
+              min={0}
+              value={conflictWindow}
+              onChange={(e) => setConflictWindow(e.target.value)}
+              className="w-full"
+            />
+            <p className="text-xs text-gray-500 mt-1">
+              ※同じツールまたは同じディレクトリのスケジュールがこの幅以内に実行される場合、登録・編集時に警告します
+            </p>
+          </div>
+
+          <div className="md:col-span-2">
+            <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+              除外カレンダー
+            </label>
+            <BlackoutCalendarSelector
+              calendars={blackoutCalendars}
+              selected={blackoutIds}
+              onChange={setBlackoutIds}
+            />
+            <p className="text-xs text-gray-500 mt-1">
+              ※選択したカレンダーの日は実行をスキップします
+            </p>
+          </div>
         </div>
 
         <div className="flex gap-2">
@@ -848,9 +1848,15 @@ export function ScheduleManager({
         </div>
       </div>
 
+      <BlackoutCalendarManager
+        calendars={blackoutCalendars}
+        onChange={loadBlackoutCalendars}
+        disabled={isRunning}
+      />
+
       {message && (
         <div
-          className={`text-sm p-3 rounded ${
+          className={`text-sm p-3 rounded whitespace-pre-line ${
             message.includes("エラー")
               ? "bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-200"
               : message.includes("成功")
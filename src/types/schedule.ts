@@ -2,13 +2,49 @@ export interface RegisteredSchedule {
   tool: string;
   schedule_id: string;
   title: string;
-  execution_time: string;
+  execution_time: string[]; // HH:MM, ascending
   target_directory?: string;
   command_args?: string;
-  schedule_type: 'once' | 'daily' | 'weekly' | 'interval';
+  schedule_type: ScheduleType;
   interval_value?: number;
+  interval_unit?: IntervalUnit; // defaults to 'days'
+  active_start?: string; // HH:MM, sub-daily intervals only
+  active_end?: string; // HH:MM, may wrap past midnight
   start_date?: string; // YYYY-MM-DD
+  weekdays?: number[]; // 0 = Sun, 1 = Mon, ... (weekly, monthly nth weekday)
+  month_day?: number; // 1-31, or -1 for the last day of the month (monthly)
+  month_week?: number; // 1-5 = first..fifth, -1 = last (monthly nth weekday)
+  cron_expression?: string; // minute hour day-of-month month day-of-week
+  end_date?: string; // YYYY-MM-DD, last day the schedule may run
+  max_runs?: number; // unregister after this many successful runs
+  timezone?: string; // IANA name, e.g. "America/New_York"; unset = this Mac's zone
+  blackout_calendars?: string[]; // IDs of BlackoutCalendar entries to skip
+  jitter_minutes?: number; // each run starts 0..=N minutes late, stable within a day
+  enabled: boolean; // false = paused: kept registered but not loaded into launchd
+  after_tool?: string; // tool of the upstream schedule; unset on chains saved before it was recorded
+  after_schedule_id?: string; // upstream schedule a 'chain' schedule runs after
+  after_status?: ChainTriggerStatus; // defaults to 'success'
   created_at: string;
+  updated_at?: string; // last change made from the UI or an import
+  last_modified_by?: 'ui' | 'import';
+}
+
+export interface BlackoutRange {
+  start: string; // YYYY-MM-DD
+  end: string; // YYYY-MM-DD, inclusive
+  yearly: boolean; // repeat every year, comparing month and day only
+}
+
+export interface BlackoutCalendar {
+  id: string;
+  name: string;
+  dates: string[]; // YYYY-MM-DD
+  ranges: BlackoutRange[];
+}
+
+export interface IcsImport {
+  calendar: BlackoutCalendar;
+  skipped: string[]; // recurring events that could not be imported
 }
 
 export interface ScheduleResult {
@@ -16,6 +52,18 @@ export interface ScheduleResult {
   message: string;
   registered_tool?: string;
   schedule_id?: string;
+  warnings?: ScheduleConflict[]; // nearby runs of schedules for the same tool or directory
+}
+
+export type ConflictReason = 'same_tool' | 'same_directory' | 'same_tool_and_directory';
+
+export interface ScheduleConflict {
+  schedule_id: string;
+  title: string;
+  tool: string;
+  reason: ConflictReason;
+  scheduled_at: string; // first clashing run of the registered schedule (RFC 3339)
+  other_scheduled_at: string; // the other schedule's run it clashes with (RFC 3339)
 }
 
 export interface ScheduleHistoryEntry {
@@ -25,4 +73,55 @@ export interface ScheduleHistoryEntry {
   status: string;
 }
 
-export type ScheduleType = 'once' | 'daily' | 'weekly' | 'interval';
+export type ScheduleDriftKind =
+  | 'missing-config'
+  | 'config-differs'
+  | 'missing-agent'
+  | 'agent-differs'
+  | 'install-differs'
+  | 'not-loaded'
+  | 'paused-but-loaded'
+  | 'orphan-file'
+  | 'orphan-job';
+
+// A job that no longer matches its registered schedule, from reconcile_schedules
+export interface ScheduleDrift {
+  tool: string;
+  schedule_id: string;
+  kind: ScheduleDriftKind;
+  repaired: boolean;
+  error?: string | null; // why the repair failed
+}
+
+export type OccurrenceStatus = 'scheduled' | 'ran' | 'failed' | 'skipped' | 'missed';
+
+/** One expected run returned by get_schedule_calendar */
+export interface CalendarOccurrence {
+  schedule_id: string;
+  tool: string;
+  title: string;
+  scheduled_at: string; // RFC 3339 in the schedule's zone
+  status: OccurrenceStatus;
+  history_status?: string; // history entry the status was derived from
+}
+
+export type ScheduleType = 'once' | 'daily' | 'weekly' | 'monthly' | 'interval' | 'cron' | 'chain';
+
+export type ChainTriggerStatus = 'success' | 'failure' | 'any';
+
+export interface ChainTrigger {
+  tool: string; // upstream tool, '' = not chosen yet
+  scheduleId: string; // upstream schedule_id, '' = not chosen yet
+  status: ChainTriggerStatus;
+}
+
+export type IntervalUnit = 'days' | 'hours' | 'minutes';
+
+export type MonthlyMode = 'day' | 'last' | 'weekday';
+
+export interface MonthlyRule {
+  mode: MonthlyMode;
+  day: number; // used when mode is 'day'
+  week: number; // 1-5 or -1, used when mode is 'weekday'
+  weekday: number; // 0 = Sun, used when mode is 'weekday'
+}